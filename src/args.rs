@@ -0,0 +1,388 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    time::Duration,
+};
+
+use advent_of_code_template::{etc::params::Params, output::Format, registry::Days};
+
+pub const USAGE: &str = "\
+Usage: cargo run --release -- [verify|bench] [DAYS...] [OPTIONS]
+       cargo run --release -- new <DAY> [--title <title>] [--year <year>]
+       cargo run --release -- gen <DAY> --size <n> [--seed <n>]
+
+DAYS is any mix of `3`, `3-7`, `1,4,9` and `all` (the default).
+
+Options:
+  --part 1|2               only report one part
+  --test                   use each day's example input
+  --all-inputs             run each day against every *.txt in its input directory
+                           and check the answers recorded for them
+  --input <path>           read the input from <path>, for a single day; `-` reads
+                           stdin, which is also used when a single day's input is piped
+  --year <year>            pick the event (default: the latest)
+  --param <name>=<value>   set a puzzle parameter of the selected days, like day 15's
+                           row `y`, over its default and the one in answers.json
+  --format text|json|csv   output format of a run (default: text)
+  --stats                  print the counters each part records, like states searched
+  --timeout <seconds>      give up on a day that runs longer and report it as TIMEOUT
+  --timeout <day>=<seconds>
+                           the same for one day, overriding the global budget
+  --baseline <label>       compare a run with the latest one saved under <label>
+                           (default: the previous run of the same input)
+  --label <label>          save a run in the history under <label>
+  --threshold <percent>    slowdown of a day that counts as a regression (default: 20)
+  --fail-on-regression     exit with an error if any day regressed
+  --no-history             do not record a run or compare it with the history
+  --runs <n>               bench: measured runs per day (default: 10)
+  --warmup <n>             bench: unmeasured runs per day (default: 1)
+  --title <title>          new: the puzzle's title
+  --size <n>               gen: how big an input to generate, e.g. lines or grid width
+  --seed <n>               gen: seed of the generator (default: 0)
+  --help                   print this message";
+
+pub enum Command {
+    Run,
+    Verify,
+    Bench,
+    New,
+    Gen,
+    Help,
+}
+
+pub struct Options {
+    pub command: Command,
+    pub year: Option<u16>,
+    pub days: Days,
+    pub part: Option<u8>,
+    pub input: Option<PathBuf>,
+    pub test: bool,
+    pub all_inputs: bool,
+    pub stats: bool,
+    pub params: Params,
+    pub timeout: Option<Duration>,
+    pub day_timeouts: BTreeMap<u8, Duration>,
+    pub runs: usize,
+    pub warmup: usize,
+    pub format: Format,
+    pub title: Option<String>,
+    pub size: Option<usize>,
+    pub seed: u64,
+    pub history: bool,
+    pub baseline: Option<String>,
+    pub label: Option<String>,
+    pub threshold: f64,
+    pub fail_on_regression: bool,
+}
+
+impl Options {
+    /// How long `day` may run, if it has a budget.
+    pub fn timeout(&self, day: u8) -> Option<Duration> {
+        self.day_timeouts.get(&day).copied().or(self.timeout)
+    }
+
+    /// The parts to report, 1 and 2 unless `--part` picked one.
+    pub fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+fn value<'a>(iter: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<&'a str, String> {
+    iter.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("{} requires a value", flag))
+}
+
+fn number<T: std::str::FromStr>(text: &str, flag: &str) -> Result<T, String> {
+    text.parse()
+        .map_err(|_| format!("{} expects a number, not {:?}", flag, text))
+}
+
+fn duration(text: &str, flag: &str) -> Result<Duration, String> {
+    number::<f64>(text, flag)
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|budget| !budget.is_zero())
+        .ok_or_else(|| {
+            format!(
+                "{} expects a positive number of seconds, not {:?}",
+                flag, text
+            )
+        })
+}
+
+/// Parses the arguments after the program name.
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        command: Command::Run,
+        year: None,
+        days: Days::All,
+        part: None,
+        input: None,
+        test: false,
+        all_inputs: false,
+        stats: false,
+        params: Params::default(),
+        timeout: None,
+        day_timeouts: BTreeMap::new(),
+        runs: 10,
+        warmup: 1,
+        format: Format::Text,
+        title: None,
+        size: None,
+        seed: 0,
+        history: true,
+        baseline: None,
+        label: None,
+        threshold: 20.0,
+        fail_on_regression: false,
+    };
+    let mut selections = Vec::new();
+
+    let mut iter = args.iter().peekable();
+    match iter.peek().map(|arg| arg.as_str()) {
+        Some("verify") => options.command = Command::Verify,
+        Some("bench") => options.command = Command::Bench,
+        Some("new") => options.command = Command::New,
+        Some("gen") => options.command = Command::Gen,
+        _ => (),
+    }
+    if !matches!(options.command, Command::Run) {
+        iter.next();
+    }
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--help" | "-h" => options.command = Command::Help,
+            "--test" => options.test = true,
+            "--all-inputs" => options.all_inputs = true,
+            "--stats" => options.stats = true,
+            "--timeout" => {
+                let text = value(&mut iter, arg)?;
+                match text.split_once('=') {
+                    Some((day, seconds)) => {
+                        let day = match parse_days(day)? {
+                            Days::Only(days) if days.len() == 1 => *days.first().unwrap(),
+                            _ => {
+                                return Err(format!(
+                                    "--timeout expects a single day, not {:?}",
+                                    day
+                                ))
+                            }
+                        };
+                        options.day_timeouts.insert(day, duration(seconds, arg)?);
+                    }
+                    None => options.timeout = Some(duration(text, arg)?),
+                }
+            }
+            "--param" => options.params.extend(&value(&mut iter, arg)?.parse()?),
+            "--year" => options.year = Some(number(value(&mut iter, arg)?, arg)?),
+            "--input" => options.input = Some(PathBuf::from(value(&mut iter, arg)?)),
+            "--runs" => options.runs = number(value(&mut iter, arg)?, arg)?,
+            "--warmup" => options.warmup = number(value(&mut iter, arg)?, arg)?,
+            "--format" => options.format = value(&mut iter, arg)?.parse()?,
+            "--title" => options.title = Some(value(&mut iter, arg)?.to_owned()),
+            "--size" => options.size = Some(number(value(&mut iter, arg)?, arg)?),
+            "--seed" => options.seed = number(value(&mut iter, arg)?, arg)?,
+            "--no-history" => options.history = false,
+            "--baseline" => options.baseline = Some(value(&mut iter, arg)?.to_owned()),
+            "--label" => options.label = Some(value(&mut iter, arg)?.to_owned()),
+            "--threshold" => options.threshold = number(value(&mut iter, arg)?, arg)?,
+            "--fail-on-regression" => options.fail_on_regression = true,
+            "--part" => match value(&mut iter, arg)? {
+                "1" => options.part = Some(1),
+                "2" => options.part = Some(2),
+                other => return Err(format!("--part expects 1 or 2, not {:?}", other)),
+            },
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            days => selections.push(parse_days(days)?),
+        }
+    }
+
+    if !selections.is_empty() && !selections.contains(&Days::All) {
+        let days = selections.into_iter().flat_map(|days| match days {
+            Days::Only(days) => days,
+            Days::All => unreachable!(),
+        });
+        options.days = Days::Only(days.collect());
+    }
+
+    let single_day = matches!(&options.days, Days::Only(days) if days.len() == 1);
+    if options.input.is_some() && !single_day {
+        return Err("--input can only be used with a single day".to_owned());
+    }
+    if matches!(options.command, Command::New) && !single_day {
+        return Err("new takes a single day".to_owned());
+    }
+    if matches!(options.command, Command::Gen) {
+        if !single_day {
+            return Err("gen takes a single day".to_owned());
+        }
+        if options.size.is_none() {
+            return Err("gen requires --size".to_owned());
+        }
+    }
+    if options.all_inputs {
+        if options.input.is_some() || options.test {
+            return Err("--all-inputs cannot be combined with --input or --test".to_owned());
+        }
+        if !matches!(options.command, Command::Run) || options.format != Format::Text {
+            return Err("--all-inputs only works for a run in the text format".to_owned());
+        }
+    }
+    if options.stats && (!matches!(options.command, Command::Run) || options.format != Format::Text)
+    {
+        return Err("--stats only works for a run in the text format".to_owned());
+    }
+    if !options.params.is_empty() && !matches!(options.command, Command::Run | Command::Bench) {
+        return Err("--param only works for a run or bench".to_owned());
+    }
+    let timeouts = options.timeout.is_some() || !options.day_timeouts.is_empty();
+    if timeouts && !matches!(options.command, Command::Run) {
+        return Err("--timeout only works for a run".to_owned());
+    }
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".to_owned());
+    }
+    if options.threshold.is_nan() || options.threshold < 0.0 {
+        return Err("--threshold must be a percentage of at least 0".to_owned());
+    }
+
+    Ok(options)
+}
+
+/// Parses one selection: `all`, or a comma separated list of days and ranges like `1,4-6`.
+pub fn parse_days(text: &str) -> Result<Days, String> {
+    if text == "all" {
+        return Ok(Days::All);
+    }
+
+    let day = |s: &str| match s.parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("{:?} is not a day between 1 and 25", s)),
+    };
+
+    let mut days = BTreeSet::new();
+    for item in text.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (day(from)?, day(to)?);
+                if from > to {
+                    return Err(format!("{:?} is an empty range", item));
+                }
+                days.extend(from..=to);
+            }
+            None => {
+                days.insert(day(item)?);
+            }
+        }
+    }
+    Ok(Days::Only(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn only(days: &[u8]) -> Days {
+        Days::Only(days.iter().copied().collect())
+    }
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn selections() {
+        assert_eq!(parse_days("3"), Ok(only(&[3])));
+        assert_eq!(parse_days("3-7"), Ok(only(&[3, 4, 5, 6, 7])));
+        assert_eq!(parse_days("1,4,9"), Ok(only(&[1, 4, 9])));
+        assert_eq!(parse_days("9,1-2,2"), Ok(only(&[1, 2, 9])));
+        assert_eq!(parse_days("all"), Ok(Days::All));
+    }
+
+    #[test]
+    fn bad_selections() {
+        for text in ["0", "26", "x", "7-3", "1,", "-3", "1--2"] {
+            assert!(parse_days(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn arguments_are_combined() {
+        let options = parse_args(&args("bench 1 5-6 --part 2 --runs 3")).unwrap();
+
+        assert!(matches!(options.command, Command::Bench));
+        assert_eq!(options.days, only(&[1, 5, 6]));
+        assert_eq!(options.parts(), [2]);
+        assert_eq!(options.runs, 3);
+
+        let options = parse_args(&args("4 all")).unwrap();
+        assert_eq!(options.days, Days::All);
+
+        let options = parse_args(&args("gen 20 --size 1000000 --seed 7")).unwrap();
+        assert!(matches!(options.command, Command::Gen));
+        assert_eq!((options.size, options.seed), (Some(1_000_000), 7));
+
+        let options =
+            parse_args(&args("--baseline fast --threshold 5 --fail-on-regression")).unwrap();
+        assert_eq!(options.baseline.as_deref(), Some("fast"));
+        assert_eq!(options.threshold, 5.0);
+        assert!(options.fail_on_regression && options.history);
+
+        let options = parse_args(&args("--timeout 2.5 --timeout 16=60")).unwrap();
+        assert_eq!(options.timeout(1), Some(Duration::from_millis(2500)));
+        assert_eq!(options.timeout(16), Some(Duration::from_secs(60)));
+        assert_eq!(
+            parse_args(&args("--timeout 16=60")).unwrap().timeout(1),
+            None
+        );
+
+        let options = parse_args(&args("15 --param y=10 --param size=20 --param y=11")).unwrap();
+        assert_eq!(
+            options.params.iter().collect::<Vec<_>>(),
+            [("y", "11"), ("size", "20")]
+        );
+    }
+
+    #[test]
+    fn bad_arguments() {
+        for line in [
+            "new",
+            "new 3-4",
+            "--part 3",
+            "--year",
+            "--runs x",
+            "--bogus",
+            "1-3 --input a.txt",
+            "--input a.txt",
+            "gen 20",
+            "gen 1-2 --size 5",
+            "gen 20 --size 5 --seed -1",
+            "--threshold -5",
+            "--threshold NaN",
+            "--all-inputs --test",
+            "bench --all-inputs",
+            "--all-inputs --format csv",
+            "verify --stats",
+            "--stats --format json",
+            "--timeout 0",
+            "--timeout x",
+            "--timeout 1-2=5",
+            "--timeout 3=-1",
+            "bench --timeout 5",
+            "--param y",
+            "--param =3",
+            "verify --param y=10",
+        ] {
+            assert!(
+                parse_args(&args(line)).is_err(),
+                "{:?} should be rejected",
+                line
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+
+use crate::etc::{
+    error::SolveError,
+    params::Params,
+    solver::{millis, Solver, Timings},
+};
+
+/// Spread of a set of timings, all in the same phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Percentiles {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Percentiles {
+    /// Uses the nearest-rank method, so each percentile is always one of the samples.
+    pub fn new(mut samples: Vec<Duration>) -> Option<Percentiles> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+
+        let rank = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Some(Percentiles {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// Percentiles for each phase of one day, over every measured run.
+pub struct Report {
+    pub parse: Percentiles,
+    pub part_one: Percentiles,
+    pub part_two: Percentiles,
+    pub total: Percentiles,
+}
+
+impl Report {
+    fn new(runs: &[Timings]) -> Option<Report> {
+        let phase = |f: fn(&Timings) -> Duration| Percentiles::new(runs.iter().map(f).collect());
+
+        Some(Report {
+            parse: phase(|t| t.parse)?,
+            part_one: phase(|t| t.part_one)?,
+            part_two: phase(|t| t.part_two)?,
+            total: phase(Timings::total)?,
+        })
+    }
+}
+
+/// One row of min, median, p95 and max per phase, under a header.
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "  {:<8} {:>12} {:>12} {:>12} {:>12}",
+            "", "min", "median", "p95", "max"
+        )?;
+        for (name, percentiles) in [
+            ("parse", &self.parse),
+            ("part 1", &self.part_one),
+            ("part 2", &self.part_two),
+            ("total", &self.total),
+        ] {
+            write!(
+                f,
+                "\n  · {:<6} {:>9.4} ms {:>9.4} ms {:>9.4} ms {:>9.4} ms",
+                name,
+                millis(percentiles.min),
+                millis(percentiles.median),
+                millis(percentiles.p95),
+                millis(percentiles.max)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves `input` with `params` `warmup` times without measuring, then `runs` times.
+/// `runs` must not be 0.
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    params: &Params,
+    warmup: usize,
+    runs: usize,
+) -> Result<Report, SolveError> {
+    for _ in 0..warmup {
+        solver.solve_with(input, params, false)?;
+    }
+
+    let timings = (0..runs)
+        .map(|_| {
+            solver
+                .solve_with(input, params, false)
+                .map(|(_, timings, _)| timings)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Report::new(&timings).expect("at least one run"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let percentiles = Percentiles::new(samples).unwrap();
+
+        assert_eq!(percentiles.min, Duration::from_millis(1));
+        assert_eq!(percentiles.median, Duration::from_millis(10));
+        assert_eq!(percentiles.p95, Duration::from_millis(19));
+        assert_eq!(percentiles.max, Duration::from_millis(20));
+    }
+
+    #[test]
+    fn single_sample() {
+        let percentiles = Percentiles::new(vec![Duration::from_millis(3)]).unwrap();
+
+        assert_eq!(percentiles.median, percentiles.p95);
+        assert_eq!(Percentiles::new(Vec::new()), None);
+    }
+}
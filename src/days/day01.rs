@@ -1,20 +1,56 @@
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
 use itertools::Itertools;
-use crate::{Solution, SolutionPair};
-
-pub fn solve(input: &str) -> SolutionPair {
-    let elf_calories = input
-        .split("\r\n\r\n")
-        .map(|elf| 
-            elf.lines()
-                .map(|s| s.parse::<usize>().unwrap_or_default())
-                .sum::<usize>()
-        )
-        .sorted()
-        .rev()
-        .collect::<Vec<_>>();
-
-    let p1 = elf_calories[0];
-    let p2 = elf_calories[0..3].iter().sum();
-
-    (Solution::USize(p1), Solution::USize(p2))
+
+pub const PUZZLE: Puzzle<Day01> = Puzzle::new(super::YEAR, 1, "Calorie Counting");
+
+pub struct Day01;
+
+impl Day for Day01 {
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, SolveError> {
+        let elf_calories = input::blocks(input)
+            .map(|elf| {
+                input::lines(elf)
+                    .map(|s| error::parse::<usize>(input, s))
+                    .sum::<Result<usize, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .sorted()
+            .rev()
+            .collect::<Vec<_>>();
+
+        if elf_calories.len() < 3 {
+            return Err(SolveError::new("expected at least three elves"));
+        }
+
+        Ok(elf_calories)
+    }
+
+    fn part_one(elf_calories: &Vec<usize>) -> Result<Solution, SolveError> {
+        Ok(Solution::USize(elf_calories[0]))
+    }
+
+    fn part_two(elf_calories: &Vec<usize>) -> Result<Solution, SolveError> {
+        Ok(Solution::USize(elf_calories[0..3].iter().sum()))
+    }
+
+    /// `size` elves carrying 1 to 12 items each.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let elves = (0..size.max(3)).map(|_| {
+            (0..rng.range(1..=12))
+                .map(|_| rng.range(1_000..=60_000))
+                .join("\n")
+        });
+        Some(elves.collect::<Vec<_>>().join("\n\n"))
+    }
 }
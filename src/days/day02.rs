@@ -1,4 +1,14 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day02> = Puzzle::new(super::YEAR, 2, "Rock Paper Scissors");
 
 const WIN: usize = 6;
 const DRAW: usize = 3;
@@ -8,25 +18,26 @@ const ROCK: usize = 1;
 const PAPER: usize = 2;
 const SCISSOR: usize = 3;
 
-
-enum Choice {
+#[derive(Clone, Copy)]
+pub enum Choice {
     Rock = 0,
     Paper = 1,
-    Scissor = 2
+    Scissor = 2,
 }
 
-enum Outcome {
+#[derive(Clone, Copy)]
+pub enum Outcome {
     Win = 0,
     Draw = 1,
     Loss = 2,
 }
 
-fn parse_choice(c: char) -> Choice {
+fn parse_choice(input: &str, c: &str) -> Result<Choice, SolveError> {
     match c {
-        'X' | 'A' => Choice::Rock,
-        'Y' | 'B' => Choice::Paper,
-        'Z' | 'C' => Choice::Scissor,
-        _ => panic!("parse error")
+        "X" | "A" => Ok(Choice::Rock),
+        "Y" | "B" => Ok(Choice::Paper),
+        "Z" | "C" => Ok(Choice::Scissor),
+        _ => Err(SolveError::at(input, c, "expected A, B, C, X, Y or Z")),
     }
 }
 
@@ -35,12 +46,12 @@ fn score_round_p1(rules: &[Vec<usize>], round: (Choice, Choice)) -> usize {
     rules[r as usize][c as usize]
 }
 
-fn parse_outcome(c: char) -> Outcome {
+fn parse_outcome(input: &str, c: &str) -> Result<Outcome, SolveError> {
     match c {
-        'X' => Outcome::Win,
-        'Y' => Outcome::Draw,
-        'Z' => Outcome::Loss,
-        _ => panic!("parse error")
+        "X" => Ok(Outcome::Win),
+        "Y" => Ok(Outcome::Draw),
+        "Z" => Ok(Outcome::Loss),
+        _ => Err(SolveError::at(input, c, "expected X, Y or Z")),
     }
 }
 
@@ -49,44 +60,64 @@ fn score_round_p2(rules: &[Vec<usize>], round: (Choice, Outcome)) -> usize {
     rules[r as usize][o as usize]
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let p1_rules = vec![
-        vec![ROCK + DRAW, PAPER + WIN,  SCISSOR + LOSS],    // opponent picks rock
-        vec![ROCK + LOSS, PAPER + DRAW, SCISSOR + WIN],     // opponent picks paper
-        vec![ROCK + WIN,  PAPER + LOSS, SCISSOR + DRAW],    // opponent picks scissor
-    ];
-
-    let p2_rules = vec![
-        vec![SCISSOR + LOSS, ROCK + DRAW, PAPER + WIN],     // opponent picks rock
-        vec![ROCK + LOSS, PAPER + DRAW, SCISSOR + WIN],     // opponent picks paper
-        vec![PAPER + LOSS,  SCISSOR + DRAW, ROCK + WIN],    // opponent picks scissor
-    ];
-
-    let rounds = input
-        .lines()
-        .into_iter()
-        .map(|l| {
-            let bytes = l.as_bytes();
-            let opponent_choice = bytes[0] as char;
-            let my_choice = bytes[2] as char;
-            (opponent_choice, my_choice)
-        })
-        .collect::<Vec<_>>();
-    
-    // Your solution here...
-    let p1 = rounds.iter()
-        .map(|p| {
-            let choices = (parse_choice(p.0), parse_choice(p.1));
-            score_round_p1(&p1_rules, choices)
-        })
-        .sum();
-
-    let p2 = rounds.iter()
-        .map(|p| {
-            let choices = (parse_choice(p.0), parse_outcome(p.1));
-            score_round_p2(&p2_rules, choices)
-        })
-        .sum();
-
-    (Solution::USize(p1), Solution::USize(p2))
+pub struct Day02;
+
+impl Day for Day02 {
+    type Input<'a> = Vec<(Choice, Choice, Outcome)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, SolveError> {
+        input::lines(input)
+            .map(|l| {
+                let (opponent, me) = error::split_once(input, l, " ")?;
+                let opponent = parse_choice(input, opponent)?;
+                Ok((
+                    opponent,
+                    parse_choice(input, me)?,
+                    parse_outcome(input, me)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn part_one(rounds: &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p1_rules = vec![
+            vec![ROCK + DRAW, PAPER + WIN, SCISSOR + LOSS], // opponent picks rock
+            vec![ROCK + LOSS, PAPER + DRAW, SCISSOR + WIN], // opponent picks paper
+            vec![ROCK + WIN, PAPER + LOSS, SCISSOR + DRAW], // opponent picks scissor
+        ];
+
+        let p1 = rounds
+            .iter()
+            .map(|&(opponent, me, _)| score_round_p1(&p1_rules, (opponent, me)))
+            .sum();
+
+        Ok(Solution::USize(p1))
+    }
+
+    fn part_two(rounds: &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p2_rules = vec![
+            vec![SCISSOR + LOSS, ROCK + DRAW, PAPER + WIN], // opponent picks rock
+            vec![ROCK + LOSS, PAPER + DRAW, SCISSOR + WIN], // opponent picks paper
+            vec![PAPER + LOSS, SCISSOR + DRAW, ROCK + WIN], // opponent picks scissor
+        ];
+
+        let p2 = rounds
+            .iter()
+            .map(|&(opponent, _, outcome)| score_round_p2(&p2_rules, (opponent, outcome)))
+            .sum();
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` rounds.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let rounds = (0..size).map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&["A", "B", "C"]),
+                rng.pick(&["X", "Y", "Z"])
+            )
+        });
+        Some(rounds.collect::<Vec<_>>().join("\n"))
+    }
 }
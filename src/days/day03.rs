@@ -1,6 +1,16 @@
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::SolveError,
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day03> = Puzzle::new(super::YEAR, 3, "Rucksack Reorganization");
 
 fn value(c: u8) -> usize {
     match c {
@@ -15,7 +25,7 @@ fn find_match(line: &[u8]) -> Option<u8> {
     for c1 in &line[..half] {
         for c2 in &line[half..] {
             if c1 == c2 {
-                return Some(*c1)
+                return Some(*c1);
             }
         }
     }
@@ -28,7 +38,7 @@ fn find_overlap(elf1: &[u8], elf2: &[u8], elf3: &[u8]) -> Option<u8> {
             if c1 == c2 {
                 for c3 in elf3 {
                     if c1 == c3 {
-                        return Some(*c1)
+                        return Some(*c1);
                     }
                 }
             }
@@ -37,21 +47,63 @@ fn find_overlap(elf1: &[u8], elf2: &[u8], elf3: &[u8]) -> Option<u8> {
     None
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let lines = input.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+fn parse_rucksack<'a>(input: &str, line: &'a str) -> Result<&'a [u8], SolveError> {
+    match line.find(|c: char| !c.is_ascii_alphabetic()) {
+        Some(i) => Err(SolveError::at(input, &line[i..], "expected an item letter")),
+        None => Ok(line.as_bytes()),
+    }
+}
+
+pub struct Day03;
 
-    let p1: usize = lines
-        .iter()
-        .filter_map(|line| find_match(line))
-        .map(value)
-        .sum();
+impl Day for Day03 {
+    type Input<'a> = Vec<&'a [u8]>;
 
-    let p2: usize = lines
-        .iter()
-        .tuples()
-        .filter_map(|(elf1,elf2,elf3)| find_overlap(elf1, elf2, elf3))
-        .map(value)
-        .sum();
+    fn parse(input: &str) -> Result<Vec<&[u8]>, SolveError> {
+        input::lines(input)
+            .map(|l| parse_rucksack(input, l))
+            .collect()
+    }
+
+    fn part_one(lines: &Vec<&[u8]>) -> Result<Solution, SolveError> {
+        let p1: usize = lines
+            .iter()
+            .filter_map(|line| find_match(line))
+            .map(value)
+            .sum();
+
+        Ok(Solution::USize(p1))
+    }
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn part_two(lines: &Vec<&[u8]>) -> Result<Solution, SolveError> {
+        let p2: usize = lines
+            .iter()
+            .tuples()
+            .filter_map(|(elf1, elf2, elf3)| find_overlap(elf1, elf2, elf3))
+            .map(value)
+            .sum();
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` rucksacks, rounded up to whole groups of three. Every rucksack has an item in
+    /// both compartments and every group shares a badge.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+        let mut rucksacks = Vec::with_capacity(size + 2);
+        for _ in 0..size.div_ceil(3) {
+            let badge = *rng.pick(ITEMS);
+            for _ in 0..3 {
+                let half = rng.range(4..=16) as usize;
+                let mut items = (0..2 * half).map(|_| *rng.pick(ITEMS)).collect::<Vec<_>>();
+                let shared = *rng.pick(ITEMS);
+                items[rng.below(half as u64) as usize] = shared;
+                items[half + rng.below(half as u64) as usize] = shared;
+                items[rng.below(2 * half as u64) as usize] = badge;
+                rucksacks.push(String::from_utf8(items).unwrap());
+            }
+        }
+        Some(rucksacks.join("\n"))
+    }
 }
@@ -1,40 +1,71 @@
 use std::ops::RangeInclusive;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
 
-fn parse_range(r: &str) -> RangeInclusive<i32> {
-    let (a, b) = r.split_once('-').unwrap();
-    let from = a.parse().unwrap();
-    let to = b.parse().unwrap();
-    from..=to
+pub const PUZZLE: Puzzle<Day04> = Puzzle::new(super::YEAR, 4, "Camp Cleanup");
+
+fn parse_range(input: &str, r: &str) -> Result<RangeInclusive<i32>, SolveError> {
+    let (a, b) = error::split_once(input, r, "-")?;
+    let from = error::parse(input, a)?;
+    let to = error::parse(input, b)?;
+    Ok(from..=to)
 }
 
 fn check_full_overlap(l: &RangeInclusive<i32>, r: &RangeInclusive<i32>) -> bool {
-    l.contains(r.start()) && l.contains(r.end()) 
-    || r.contains(l.start()) && r.contains(l.end())
+    l.contains(r.start()) && l.contains(r.end()) || r.contains(l.start()) && r.contains(l.end())
 }
 
 fn check_any_overlap(l: &RangeInclusive<i32>, r: &RangeInclusive<i32>) -> bool {
-    l.contains(r.start()) || l.contains(r.end()) 
-    || r.contains(l.start()) || r.contains(l.end())
+    l.contains(r.start()) || l.contains(r.end()) || r.contains(l.start()) || r.contains(l.end())
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let pairs = input
-        .lines()
-        .map(|line| {
-            let (elf1, elf2) = line.split_once(',').unwrap();
-            (parse_range(elf1), parse_range(elf2))
-        })
-        .collect::<Vec<_>>();
-
-    let p1 = pairs.iter()
-        .filter(|(r1,r2)| check_full_overlap(r1, r2))
-        .count();
-    
-    let p2 = pairs.iter()
-        .filter(|(r1,r2)| check_any_overlap(r1, r2))
-        .count();
-
-    (Solution::USize(p1), Solution::USize(p2))
+pub struct Day04;
+
+impl Day for Day04 {
+    type Input<'a> = Vec<(RangeInclusive<i32>, RangeInclusive<i32>)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, SolveError> {
+        input::lines(input)
+            .map(|line| {
+                let (elf1, elf2) = error::split_once(input, line, ",")?;
+                Ok((parse_range(input, elf1)?, parse_range(input, elf2)?))
+            })
+            .collect()
+    }
+
+    fn part_one(pairs: &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p1 = pairs
+            .iter()
+            .filter(|(r1, r2)| check_full_overlap(r1, r2))
+            .count();
+
+        Ok(Solution::USize(p1))
+    }
+
+    fn part_two(pairs: &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p2 = pairs
+            .iter()
+            .filter(|(r1, r2)| check_any_overlap(r1, r2))
+            .count();
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` pairs of sections between 1 and 99.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut range = || {
+            let from = rng.range(1..=99);
+            format!("{}-{}", from, rng.range(from..=99))
+        };
+        let pairs = (0..size).map(|_| format!("{},{}", range(), range()));
+        Some(pairs.collect::<Vec<_>>().join("\n"))
+    }
 }
@@ -1,98 +1,134 @@
 use std::collections::VecDeque;
 
-use crate::{Solution, SolutionPair};
-
-fn parse_state(state: &str) -> Vec<Vec<char>> {
-    let mut iterator = state.lines().rev();
-    let heading = iterator.next().expect("Failed to get header");
-
-    let stack_count = heading
-        .trim_end()
-        .chars()
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day05> = Puzzle::new(super::YEAR, 5, "Supply Stacks");
+
+fn parse_state(input: &str, state: &str) -> Result<Vec<Vec<char>>, SolveError> {
+    let mut iterator = input::lines(state).rev();
+    let heading = iterator
+        .next()
+        .ok_or_else(|| SolveError::new("expected a drawing of the stacks"))?;
+
+    let last = heading
+        .split_whitespace()
         .last()
-        .expect("Failed to get last")
-        .to_digit(10)
-        .expect("Wasn't a digit") as usize;
+        .ok_or_else(|| SolveError::at(input, heading, "expected stack numbers"))?;
+    let stack_count: usize = error::parse(input, last)?;
 
     let mut stacks = vec![vec![]; stack_count];
 
     for line in iterator {
         for (stack, c) in line.chars().skip(1).step_by(4).enumerate() {
             if c.is_alphabetic() {
-                stacks[stack].push(c);
+                stacks
+                    .get_mut(stack)
+                    .ok_or_else(|| {
+                        SolveError::at(input, line, "crate outside of the numbered stacks")
+                    })?
+                    .push(c);
             }
         }
     }
-    stacks
+    Ok(stacks)
 }
 
 #[derive(Debug)]
-struct Instruction {
+pub struct Instruction {
     amount: usize,
     from: usize,
-    to: usize
+    to: usize,
 }
 
-fn part_one(mut state: Vec<Vec<char>>, instructions: &Vec<Instruction>) -> String {
+fn part_one(
+    mut state: Vec<Vec<char>>,
+    instructions: &Vec<Instruction>,
+) -> Result<String, SolveError> {
     for instruction in instructions {
         for _ in 0..instruction.amount {
-            let v = state[instruction.from-1].pop().expect("No item to pop");
-            state[instruction.to-1].push(v);
+            let v = state[instruction.from - 1].pop().ok_or_else(|| {
+                SolveError::new(format!("stack {} ran out of crates", instruction.from))
+            })?;
+            state[instruction.to - 1].push(v);
         }
     }
 
-    let chars = state
-        .iter()
-        .filter_map(|stack| stack.last());
+    let chars = state.iter().filter_map(|stack| stack.last());
 
-    String::from_iter(chars)
+    Ok(String::from_iter(chars))
 }
 
-fn part_two(mut state: Vec<Vec<char>>, instructions: &Vec<Instruction>) -> String {
+fn part_two(
+    mut state: Vec<Vec<char>>,
+    instructions: &Vec<Instruction>,
+) -> Result<String, SolveError> {
     let mut temp = VecDeque::new();
     for instruction in instructions {
         for _ in 0..instruction.amount {
-            let v = state[instruction.from-1].pop().expect("No item to pop");
+            let v = state[instruction.from - 1].pop().ok_or_else(|| {
+                SolveError::new(format!("stack {} ran out of crates", instruction.from))
+            })?;
             temp.push_front(v);
         }
 
         while let Some(v) = temp.pop_front() {
-            state[instruction.to-1].push(v);
-
+            state[instruction.to - 1].push(v);
         }
     }
 
-    let chars = state
-        .iter()
-        .filter_map(|stack| stack.last());
+    let chars = state.iter().filter_map(|stack| stack.last());
 
-    String::from_iter(chars)
+    Ok(String::from_iter(chars))
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (state, instructions) = input
-        .split_once("\r\n\r\n")
-        .expect("Invalid input for day5");
-
-    let state = parse_state(state);
-
-    let instructions = instructions
-        .lines()
-        .map(|l| {
-            let values = l.split_whitespace()
-                .filter_map(|s| s.parse::<usize>().ok())
-                .collect::<Vec<_>>();
-
-            Instruction {
-                amount: values[0],
-                from: values[1],
-                to: values[2]
-            }
-        })
-        .collect::<Vec<_>>();
+pub struct Day05;
+
+impl Day for Day05 {
+    type Input<'a> = (Vec<Vec<char>>, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, SolveError> {
+        let (state, instructions) = input::two_blocks(input).ok_or_else(|| {
+            SolveError::new("expected a blank line between the stacks and the moves")
+        })?;
+
+        let state = parse_state(input, state)?;
+        let stack = 1..=state.len();
+
+        let instructions = input::lines(instructions)
+            .map(|l| {
+                let values = l
+                    .split_whitespace()
+                    .filter_map(|s| s.parse::<usize>().ok())
+                    .collect::<Vec<_>>();
+
+                match values[..] {
+                    [amount, from, to] if stack.contains(&from) && stack.contains(&to) => {
+                        Ok(Instruction { amount, from, to })
+                    }
+                    _ => Err(SolveError::at(
+                        input,
+                        l,
+                        "expected `move <n> from <stack> to <stack>`",
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((state, instructions))
+    }
 
-    let p1 = part_one(state.clone(), &instructions);
-    let p2 = part_two(state, &instructions);
+    fn part_one((state, instructions): &Self::Input<'_>) -> Result<Solution, SolveError> {
+        part_one(state.clone(), instructions).map(Solution::Str)
+    }
 
-    (Solution::Str(p1), Solution::Str(p2))
+    fn part_two((state, instructions): &Self::Input<'_>) -> Result<Solution, SolveError> {
+        part_two(state.clone(), instructions).map(Solution::Str)
+    }
 }
@@ -1,4 +1,13 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::SolveError,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day06> = Puzzle::new(super::YEAR, 6, "Tuning Trouble");
 
 fn all_unique_bits(masks: &[u32]) -> bool {
     let mut unique = 0;
@@ -11,25 +20,57 @@ fn all_unique_bits(masks: &[u32]) -> bool {
     true
 }
 
-
-fn find_first_distinct_combination(input: &[u32], size: usize) -> usize {
+fn find_first_distinct_combination(input: &[u32], size: usize) -> Result<usize, SolveError> {
     input
         .windows(size)
         .position(all_unique_bits)
-        .expect("Expected to find a position") + size
+        .map(|i| i + size)
+        .ok_or_else(|| SolveError::new(format!("no run of {} distinct characters", size)))
 }
 
+pub struct Day06;
+
+impl Day for Day06 {
+    type Input<'a> = Vec<u32>;
 
-pub fn solve(input: &str) -> SolutionPair {
-    const ASCII_A_LOWERCASE: u8 = 97;
+    fn parse(input: &str) -> Result<Vec<u32>, SolveError> {
+        const ASCII_A_LOWERCASE: u8 = 97;
 
-    let mask_vec = input
-        .bytes()
-        .map(|c| 1_u32 << (c - ASCII_A_LOWERCASE))
-        .collect::<Vec<_>>();
+        if let Some(i) = input.find(|c: char| !c.is_ascii_lowercase()) {
+            return Err(SolveError::at(
+                input,
+                &input[i..],
+                "expected a lowercase letter",
+            ));
+        }
+
+        let mask_vec = input
+            .bytes()
+            .map(|c| 1_u32 << (c - ASCII_A_LOWERCASE))
+            .collect::<Vec<_>>();
 
-    let p1: usize = find_first_distinct_combination(&mask_vec, 4);
-    let p2: usize = find_first_distinct_combination(&mask_vec, 14);
+        Ok(mask_vec)
+    }
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn part_one(mask_vec: &Vec<u32>) -> Result<Solution, SolveError> {
+        find_first_distinct_combination(mask_vec, 4).map(Solution::USize)
+    }
+
+    fn part_two(mask_vec: &Vec<u32>) -> Result<Solution, SolveError> {
+        find_first_distinct_combination(mask_vec, 14).map(Solution::USize)
+    }
+
+    /// `size` characters drawn from eight letters, so the only start-of-message marker is the
+    /// fourteen distinct letters at the very end.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut signal = (0..size)
+            .map(|_| *rng.pick(b"abcdefgh") as char)
+            .collect::<String>();
+        signal.extend(
+            rng.sample(26, 14)
+                .into_iter()
+                .map(|i| (b'a' + i as u8) as char),
+        );
+        Some(signal)
+    }
 }
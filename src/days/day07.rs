@@ -1,50 +1,83 @@
-use std::{collections::{HashMap}, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf};
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day07> = Puzzle::new(super::YEAR, 7, "No Space Left On Device");
 
 const PART_ONE_SIZE: i32 = 100_000;
 const PART_TWO_SIZE: i32 = 30_000_000;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut dir_sizes: HashMap<String, i32> = HashMap::new();
-    let mut current_path = PathBuf::new();
-
-    let lines = input.split('$').skip(1);
-    for line in lines.map(str::trim) {
-        match line.lines().next().unwrap() {
-            "ls" => {
-                let size: i32 = line
-                    .lines()
-                    .skip(1)
-                    .filter_map(|output| {
-                        let (size, _) = output.split_once(' ').unwrap();
-                        size.parse::<i32>().ok()
-                    })
-                    .sum();
-                    
-                let current_dir = current_path.clone();
-                for dir in current_dir.ancestors() {
-                    let dir = String::from(dir.to_str().unwrap());
-                    dir_sizes.entry(dir).and_modify(|total| *total += size).or_insert(size);
+pub struct Day07;
+
+impl Day for Day07 {
+    type Input<'a> = HashMap<String, i32>;
+
+    fn parse(input: &str) -> Result<HashMap<String, i32>, SolveError> {
+        let mut dir_sizes: HashMap<String, i32> = HashMap::new();
+        let mut current_path = PathBuf::new();
+
+        let lines = input.split('$').skip(1);
+        for line in lines.map(str::trim) {
+            match input::lines(line).next().unwrap_or_default() {
+                "ls" => {
+                    let mut size = 0;
+                    for output in input::lines(line).skip(1) {
+                        let (output_size, _) = error::split_once(input, output, " ")?;
+                        if output_size != "dir" {
+                            size += error::parse::<i32>(input, output_size)?;
+                        }
+                    }
+
+                    let current_dir = current_path.clone();
+                    for dir in current_dir.ancestors() {
+                        let dir = String::from(dir.to_str().unwrap());
+                        dir_sizes
+                            .entry(dir)
+                            .and_modify(|total| *total += size)
+                            .or_insert(size);
+                    }
+                }
+                "cd .." => {
+                    current_path.pop();
+                }
+                dir_command => {
+                    let (_, dir) = error::split_once(input, dir_command, " ")?;
+                    current_path.push(dir);
                 }
-            }
-            "cd .." => { 
-                current_path.pop();
-            },
-            dir_command => {
-                let (_, dir) = dir_command.split_once(' ').unwrap();
-                current_path.push(dir);
             }
         }
+
+        if !dir_sizes.contains_key("/") {
+            return Err(SolveError::new("never listed the root directory"));
+        }
+
+        Ok(dir_sizes)
     }
 
-    let used_space = dir_sizes.get("/").copied().unwrap();
-    let p2_predicate = |s| {
-        70_000_000 - PART_TWO_SIZE + s >= used_space
-    };
+    fn part_one(dir_sizes: &HashMap<String, i32>) -> Result<Solution, SolveError> {
+        let p1: i32 = dir_sizes.values().filter(|&&s| s <= PART_ONE_SIZE).sum();
 
-    let p1: i32 = dir_sizes.values().filter(|&&s| s <= PART_ONE_SIZE).sum();
-    let p2 = dir_sizes.values().filter(|&&s| p2_predicate(s)).min().copied().unwrap();
+        Ok(Solution::I32(p1))
+    }
 
-    (Solution::I32(p1), Solution::I32(p2))
+    fn part_two(dir_sizes: &HashMap<String, i32>) -> Result<Solution, SolveError> {
+        let used_space = dir_sizes["/"];
+        let p2_predicate = |s| 70_000_000 - PART_TWO_SIZE + s >= used_space;
+
+        let p2 = dir_sizes
+            .values()
+            .filter(|&&s| p2_predicate(s))
+            .min()
+            .copied()
+            .ok_or_else(|| SolveError::new("no directory frees up enough space"))?;
+
+        Ok(Solution::I32(p2))
+    }
 }
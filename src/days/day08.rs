@@ -1,35 +1,35 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::SolveError,
+        solver::{Day, Puzzle},
+    },
+    utils::{grid::Grid, rng::Rng, vector_2d::Vector2},
+    Solution,
+};
 
-fn add(u: usize, i: isize) -> usize {
-    ((u as isize) + i) as usize
-}
-
-fn try_get_neighbor(grid: &[Vec<u32>], x: usize, y: usize, dx: isize, dy: isize) -> Option<&u32> {
-    grid.get(add(x, dx)).and_then(|x| x.get(add(y, dy)))
-}
+pub const PUZZLE: Puzzle<Day08> = Puzzle::new(super::YEAR, 8, "Treetop Tree House");
 
-fn check_visible(grid: &[Vec<u32>], x: usize, y: usize) -> (bool, u32) {
-    let tree_height = grid[x][y];
-
-    if x == 0 || y == 0 {
-        return (true, 0);
-    }
+const DIRECTIONS: [Vector2; 4] = [
+    Vector2 { x: -1, y: 0 },
+    Vector2 { x: 0, y: -1 },
+    Vector2 { x: 1, y: 0 },
+    Vector2 { x: 0, y: 1 },
+];
 
-    let dirs = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+fn check_visible(grid: &Grid<u32>, position: Vector2) -> (bool, u32) {
+    let tree_height = grid[position];
 
     let (mut invisible, mut score) = (true, 1);
 
-    for (dx, dy) in dirs {
-        let (mut x, mut y, mut i, mut visible) = (x, y, 0, true);
+    for direction in DIRECTIONS {
+        let (mut i, mut visible) = (0, true);
 
-        while let Some(&neighbor) = try_get_neighbor(grid, x, y, dx, dy) {
+        for (_, &neighbor) in grid.line_of_sight(position, direction) {
             i += 1;
             if tree_height <= neighbor {
                 visible = false;
                 break;
             }
-            x = add(x, dx);
-            y = add(y, dy);
         }
         if visible {
             invisible = false;
@@ -39,27 +39,40 @@ fn check_visible(grid: &[Vec<u32>], x: usize, y: usize) -> (bool, u32) {
     (!invisible, score)
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| char::to_digit(c, 10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+fn visible_trees(grid: &Grid<u32>) -> impl Iterator<Item = u32> + '_ {
+    grid.positions()
+        .map(|position| check_visible(grid, position))
+        .filter_map(|(visible, score)| visible.then_some(score))
+}
 
-    let mut tree_count = 0;
-    let mut max_score = 0;
-    for x in 0..grid.len() {
-        for y in 0..grid[0].len() {
-            let (visible, score) = check_visible(&grid, x, y);
-            if visible {
-                tree_count += 1;
-                max_score = max_score.max(score);
-            }
-        }
+pub struct Day08;
+
+impl Day for Day08 {
+    type Input<'a> = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Grid<u32>, SolveError> {
+        Grid::parse(input, |c| c.to_digit(10))
+    }
+
+    fn part_one(grid: &Grid<u32>) -> Result<Solution, SolveError> {
+        let tree_count = visible_trees(grid).count() as i32;
+
+        Ok(Solution::I32(tree_count))
     }
 
-    (Solution::I32(tree_count), Solution::U32(max_score))
+    fn part_two(grid: &Grid<u32>) -> Result<Solution, SolveError> {
+        let max_score = visible_trees(grid).max().unwrap_or_default();
+
+        Ok(Solution::U32(max_score))
+    }
+
+    /// A `size` by `size` forest.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let rows = (0..size.max(1)).map(|_| {
+            (0..size.max(1))
+                .map(|_| rng.range(0..=9).to_string())
+                .collect::<String>()
+        });
+        Some(rows.collect::<Vec<_>>().join("\n"))
+    }
 }
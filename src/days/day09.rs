@@ -1,38 +1,44 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::{
+        rng::Rng,
+        vector_2d::{Direction, Vector2i32, YAxis},
+    },
+    Solution,
+};
 use std::collections::HashSet;
 
-type Pair = (i32, i32);
+pub const PUZZLE: Puzzle<Day09> = Puzzle::new(super::YEAR, 9, "Rope Bridge");
 
-fn move_head((x, y): &Pair, (dx, dy): &Pair) -> (i32, i32) {
-    (x + dx, y + dy)
-}
+pub type Pair = Vector2i32;
 
-fn move_tail((hx, hy): &Pair, (tx, ty): &Pair) -> Pair {
-    let (dx, dy) = ((hx - tx), (hy - ty));
-    if dx.abs() < 2 && dy.abs() < 2 {
-        return (*tx, *ty);
+fn move_tail(head: &Pair, tail: &Pair) -> Pair {
+    let d = *head - tail;
+    if d.x.abs() < 2 && d.y.abs() < 2 {
+        return *tail;
     }
-    (tx + dx.signum(), ty + dy.signum())
+    *tail + (d.x.signum(), d.y.signum())
 }
 
-fn parse_direction(dir: &str) -> Pair {
-    match dir {
-        "U" => ( 1,  0),
-        "D" => (-1,  0),
-        "L" => ( 0, -1),
-        "R" => ( 0,  1),
-        _ => unreachable!(),
+fn parse_direction(input: &str, dir: &str) -> Result<Pair, SolveError> {
+    match dir.parse::<Direction>() {
+        Ok(direction) if matches!(dir, "U" | "D" | "L" | "R") => Ok(direction.unit(YAxis::Up)),
+        _ => Err(SolveError::at(input, dir, "expected U, D, L or R")),
     }
 }
 
-fn simulate_knots(movements: &[(i32, i32)], knots: usize) -> usize {
-    let mut knots = vec![(0, 0); knots];
+fn simulate_knots(movements: &[Pair], knots: usize) -> usize {
+    let mut knots = vec![Pair::default(); knots];
 
     let mut tail_positions = HashSet::with_capacity(movements.len());
-    tail_positions.insert((0, 0));
+    tail_positions.insert(Pair::default());
 
     for movement in movements {
-        knots[0] = move_head(&knots[0], movement);
+        knots[0] += movement;
 
         for i in 1..knots.len() {
             let head = knots[i - 1];
@@ -45,18 +51,34 @@ fn simulate_knots(movements: &[(i32, i32)], knots: usize) -> usize {
     tail_positions.len()
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let movements = input
-        .lines()
-        .flat_map(|l| {
-            let (dir, steps) = l.split_once(' ').unwrap();
-            let steps = steps.parse().unwrap();
-            (0..steps).map(|_| parse_direction(dir))
-        })
-        .collect::<Vec<_>>();
+pub struct Day09;
 
-    let p1: usize = simulate_knots(&movements, 2);
-    let p2: usize = simulate_knots(&movements, 10);
+impl Day for Day09 {
+    type Input<'a> = Vec<Pair>;
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn parse(input: &str) -> Result<Vec<Pair>, SolveError> {
+        let mut movements = Vec::new();
+        for l in input::lines(input) {
+            let (dir, steps) = error::split_once(input, l, " ")?;
+            let direction = parse_direction(input, dir)?;
+            let steps: usize = error::parse(input, steps)?;
+            movements.extend((0..steps).map(|_| direction));
+        }
+        Ok(movements)
+    }
+
+    fn part_one(movements: &Vec<Pair>) -> Result<Solution, SolveError> {
+        Ok(Solution::USize(simulate_knots(movements, 2)))
+    }
+
+    fn part_two(movements: &Vec<Pair>) -> Result<Solution, SolveError> {
+        Ok(Solution::USize(simulate_knots(movements, 10)))
+    }
+
+    /// `size` motions of 1 to 20 steps.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let motions =
+            (0..size).map(|_| format!("{} {}", rng.pick(&["U", "D", "L", "R"]), rng.range(1..=20)));
+        Some(motions.collect::<Vec<_>>().join("\n"))
+    }
 }
@@ -1,14 +1,25 @@
 use std::ops::Index;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
 
-fn parse_line(line: &str) -> i32 {
+pub const PUZZLE: Puzzle<Day10> =
+    Puzzle::new(super::YEAR, 10, "Cathode-Ray Tube").with_test_input("test2");
+
+fn parse_line(input: &str, line: &str) -> Result<i32, SolveError> {
     match line {
-        "noop" => 0,
+        "noop" => Ok(0),
         _ => {
-            let (_, v) = line.split_once(' ').unwrap();
+            let (_, v) = error::split_once(input, line, " ")?;
 
-            v.parse().unwrap()
+            error::parse(input, v)
         }
     }
 }
@@ -21,33 +32,64 @@ fn render_screen(cycles: &[i32]) -> String {
         }
         let sprite = cycles[cycle];
         let c = if (sprite - cycle as i32 % 40).abs() < 2 {
-                '#'
-            } else {
-                ' '
-            };
+            '#'
+        } else {
+            ' '
+        };
         pixels.push(c);
     });
     pixels
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut x = 1;
-    let mut cycles = Vec::with_capacity(240);
+pub struct Day10;
+
+impl Day for Day10 {
+    /// Value of the X register during each cycle.
+    type Input<'a> = Vec<i32>;
 
-    for instruction in input.lines().map(parse_line) {
-        cycles.push(x);
-        if instruction != 0 {
+    fn parse(input: &str) -> Result<Vec<i32>, SolveError> {
+        let mut x = 1;
+        let mut cycles = Vec::with_capacity(240);
+
+        for line in input::lines(input) {
+            let instruction = parse_line(input, line)?;
             cycles.push(x);
-            x += instruction;
+            if instruction != 0 {
+                cycles.push(x);
+                x += instruction;
+            }
+        }
+
+        if cycles.len() < 240 {
+            return Err(SolveError::new("program runs for fewer than 240 cycles"));
         }
+
+        Ok(cycles)
     }
 
-    let p1 = (20..=220)
-        .step_by(40)
-        .map(|i| i as i32 * cycles.index(i - 1))
-        .sum();
+    fn part_one(cycles: &Vec<i32>) -> Result<Solution, SolveError> {
+        let p1 = (20..=220)
+            .step_by(40)
+            .map(|i| i as i32 * cycles.index(i - 1))
+            .sum();
+
+        Ok(Solution::I32(p1))
+    }
 
-    let pixels = render_screen(&cycles);
+    fn part_two(cycles: &Vec<i32>) -> Result<Solution, SolveError> {
+        Ok(Solution::Str(render_screen(cycles)))
+    }
 
-    (Solution::I32(p1), Solution::Str(pixels))
+    /// `size` instructions, at least the 240 that fill the screen.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let program = (0..size.max(240)).map(|_| {
+            if rng.one_in(3) {
+                "noop".to_owned()
+            } else {
+                // `addx 0` would be read as a `noop`.
+                format!("addx {}", rng.range(1..=5) * *rng.pick(&[-1, 1]))
+            }
+        });
+        Some(program.collect::<Vec<_>>().join("\n"))
+    }
 }
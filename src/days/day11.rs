@@ -1,8 +1,17 @@
-use std::{collections::VecDeque, iter, str::Lines};
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day11> = Puzzle::new(super::YEAR, 11, "Monkey in the Middle");
 
 #[derive(Clone, Copy, Debug)]
 enum Operation {
@@ -19,7 +28,7 @@ struct Test {
 }
 
 #[derive(Clone, Debug)]
-struct Monkey {
+pub struct Monkey {
     inspect_count: i64,
     inventory: VecDeque<u64>,
     operation: Operation,
@@ -40,63 +49,84 @@ impl Monkey {
                 Operation::Squared() => worry * worry,
             };
             let worry = adjust_worry(worry);
-            match worry % self.test.divider == 0 {
-                true => (worry, self.test.true_monkey),
-                false => (worry, self.test.false_monkey),
+            match worry % self.test.divider {
+                0 => (worry, self.test.true_monkey),
+                _ => (worry, self.test.false_monkey),
             }
         })
     }
 }
 
-fn parse_inventory(line: &str) -> VecDeque<u64> {
-    let (_, right) = line.split_once(": ").unwrap();
+fn next_line<'a>(
+    input: &str,
+    monkey: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<&'a str, SolveError> {
+    lines
+        .next()
+        .ok_or_else(|| SolveError::at(input, monkey, "monkey description ends early"))
+}
+
+fn parse_inventory(input: &str, line: &str) -> Result<VecDeque<u64>, SolveError> {
+    let (_, right) = error::split_once(input, line, ": ")?;
 
     right
         .split(", ")
-        .map(str::parse::<u64>)
-        .map(Result::unwrap)
+        .map(|item| error::parse::<u64>(input, item))
         .collect()
 }
 
-fn parse_operation(line: &str) -> Operation {
-    let (_, right) = line.split_once("new = old ").unwrap();
-    let (operation, value) = right.split_once(' ').unwrap();
+fn parse_operation(input: &str, line: &str) -> Result<Operation, SolveError> {
+    let (_, right) = error::split_once(input, line, "new = old ")?;
+    let (operation, value) = error::split_once(input, right, " ")?;
     if value == "old" {
-        return Operation::Squared();
+        return Ok(Operation::Squared());
     }
-    let value = value.parse().unwrap();
+    let value = error::parse(input, value)?;
     match operation {
-        "+" => Operation::Plus(value),
-        "*" => Operation::Multiply(value),
-        _ => unreachable!(),
+        "+" => Ok(Operation::Plus(value)),
+        "*" => Ok(Operation::Multiply(value)),
+        _ => Err(SolveError::at(input, operation, "expected + or *")),
     }
 }
 
-fn parse_test(mut take: iter::Take<Lines>) -> Test {
-    let (_, divider) = take.next().unwrap().split_once("by ").unwrap();
-    let (_, true_monkey) = take.next().unwrap().split_once("monkey ").unwrap();
-    let (_, false_monkey) = take.next().unwrap().split_once("monkey ").unwrap();
-
-    Test {
-        divider: divider.parse().unwrap(),
-        true_monkey: true_monkey.parse().unwrap(),
-        false_monkey: false_monkey.parse().unwrap(),
+fn parse_test<'a>(
+    input: &str,
+    monkey: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<Test, SolveError> {
+    let (_, divider) = error::split_once(input, next_line(input, monkey, lines)?, "by ")?;
+    let (_, true_monkey) = error::split_once(input, next_line(input, monkey, lines)?, "monkey ")?;
+    let (_, false_monkey) = error::split_once(input, next_line(input, monkey, lines)?, "monkey ")?;
+
+    let divider = error::parse(input, divider)?;
+    if divider == 0 {
+        return Err(SolveError::at(input, monkey, "divisor must not be zero"));
     }
+
+    Ok(Test {
+        divider,
+        true_monkey: error::parse(input, true_monkey)?,
+        false_monkey: error::parse(input, false_monkey)?,
+    })
 }
 
-fn parse_monkey(monkey: &str) -> Monkey {
-    let (_, lines) = monkey.split_once(":\r\n").unwrap();
-    let mut lines = lines.lines();
+fn parse_monkey(input: &str, monkey: &str) -> Result<Monkey, SolveError> {
+    let mut lines = input::lines(monkey);
+    let header = next_line(input, monkey, &mut lines)?;
+    if !header.ends_with(':') {
+        return Err(SolveError::at(input, header, "expected `Monkey <n>:`"));
+    }
 
-    let inventory = parse_inventory(lines.next().unwrap());
-    let operation = parse_operation(lines.next().unwrap());
-    let test = parse_test(lines.take(3));
-    Monkey {
+    let inventory = parse_inventory(input, next_line(input, monkey, &mut lines)?)?;
+    let operation = parse_operation(input, next_line(input, monkey, &mut lines)?)?;
+    let test = parse_test(input, monkey, &mut lines)?;
+    Ok(Monkey {
         inspect_count: 0,
         inventory,
         operation,
         test,
-    }
+    })
 }
 
 fn play_round<F: Fn(u64) -> u64>(monkeys: &mut [Monkey], adjust_worry: F) {
@@ -123,12 +153,44 @@ fn simulation<F: Fn(u64) -> u64>(mut monkeys: Vec<Monkey>, rounds: i64, adjust_w
         .product()
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let monkeys: Vec<Monkey> = input.split("\r\n\r\n").map(parse_monkey).collect();
+pub struct Day11;
+
+impl Day for Day11 {
+    type Input<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, SolveError> {
+        let monkeys = input::blocks(input)
+            .map(|monkey| parse_monkey(input, monkey))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for monkey in &monkeys {
+            let Test {
+                true_monkey,
+                false_monkey,
+                ..
+            } = monkey.test;
+            if true_monkey.max(false_monkey) >= monkeys.len() {
+                return Err(SolveError::new(format!(
+                    "monkey throws to monkey {}, but there are only {}",
+                    true_monkey.max(false_monkey),
+                    monkeys.len()
+                )));
+            }
+        }
+
+        Ok(monkeys)
+    }
 
-    let modulus: u64 = monkeys.iter().map(|m| m.test.divider).product();
-    let p1 = simulation(monkeys.clone(), 20, |w| w / 3);
-    let p2 = simulation(monkeys, 10000, |w| w % modulus);
+    fn part_one(monkeys: &Vec<Monkey>) -> Result<Solution, SolveError> {
+        let p1 = simulation(monkeys.clone(), 20, |w| w / 3);
 
-    (Solution::I64(p1), Solution::I64(p2))
+        Ok(Solution::I64(p1))
+    }
+
+    fn part_two(monkeys: &Vec<Monkey>) -> Result<Solution, SolveError> {
+        let modulus: u64 = monkeys.iter().map(|m| m.test.divider).product();
+        let p2 = simulation(monkeys.clone(), 10000, |w| w % modulus);
+
+        Ok(Solution::I64(p2))
+    }
 }
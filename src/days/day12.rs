@@ -1,97 +1,104 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::SolveError,
+        solver::{Day, Puzzle},
+    },
+    utils::{grid::Grid, rng::Rng, vector_2d::Vector2},
+    Solution,
+};
 
-type Grid = Vec<Vec<u8>>;
+pub const PUZZLE: Puzzle<Day12> = Puzzle::new(super::YEAR, 12, "Hill Climbing Algorithm");
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Position {
-    fn add(&self, (dx, dy): (isize, isize)) -> Position {
-        let x = (self.x as isize) + dx;
-        let y = (self.y as isize) + dy;
-        Position {
-            x: x as usize,
-            y: y as usize,
-        }
-    }
-}
-
-fn search(grid: &Grid, start: Position, end: Position) -> Option<u32> {
-    let mut visited = HashSet::new();
+fn search(grid: &Grid<u8>, start: Vector2, end: Vector2) -> Option<u32> {
+    let mut visited = Grid::new(grid.width(), grid.height(), false);
     let mut queue = VecDeque::new();
-    let directions = [(0, -1), (-1, 0), (0, 1), (1, 0)];
     queue.push_back((start, 0));
     while let Some((p, path_length)) = queue.pop_front() {
         if p == end {
             return Some(path_length);
         }
-        for dir in directions {
-            let p2 = p.add(dir);
-            match grid.get(p2.y).and_then(|row| row.get(p2.x)) {
-                None => continue,
-                Some(&height) => {
-                    let current = grid[p.y][p.x];
-                    let can_climb = current + 1 >= height;
-                    if can_climb && visited.insert(p2) {
-                        queue.push_back((p2, path_length + 1));
-                    }
-                }
+        for p2 in grid.neighbours(p) {
+            let can_climb = grid[p] + 1 >= grid[p2];
+            if can_climb && !visited[p2] {
+                visited[p2] = true;
+                queue.push_back((p2, path_length + 1));
             }
         }
     }
     None
 }
 
-fn find(grid: &Grid, c: u8) -> Position {
-    let height = grid.len();
-    let width = grid[0].len();
-    
-    #[allow(clippy::needless_range_loop)]
-    for y in 0..height {
-        for x in 0..width {
-            let v = grid[y][x];
-            if v == c {
-                return Position { x, y };
-            }
-        }
-    }
-    panic!("Couldn't find {}", c)
+fn find(grid: &Grid<u8>, c: u8) -> Result<Vector2, SolveError> {
+    grid.find(&c)
+        .ok_or_else(|| SolveError::new(format!("no {} marker in the heightmap", c as char)))
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut grid: Grid = input.lines().map(|line| line.as_bytes().to_vec()).collect();
-
-    let start = find(&grid, b'S');
-    let end = find(&grid, b'E');
-
-    grid[start.y][start.x] = b'a';
-
-    let p1: u32 = search(&grid, start, end).unwrap();
-
-    let candidates = grid
-        .iter()
-        .enumerate()
-        .flat_map(|(y, xs)| {
-            xs.iter().enumerate().filter_map(move |(x, &v)| {
-                if v == b'a' {
-                    Some(Position { x, y })
-                } else {
-                    None
-                }
-            })
-        })
-        .collect::<Vec<_>>();
-
-    let p2: u32 = candidates
-        .iter()
-        .filter_map(|p| search(&grid, *p, end))
-        .min()
-        .unwrap();
-
-    (Solution::U32(p1), Solution::U32(p2))
+fn no_path() -> SolveError {
+    SolveError::new("no path reaches E")
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    type Input<'a> = (Grid<u8>, Vector2, Vector2);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, SolveError> {
+        let mut grid = Grid::parse(input, |c| u8::try_from(c).ok())?;
+
+        let start = find(&grid, b'S')?;
+        let end = find(&grid, b'E')?;
+
+        grid[start] = b'a';
+        grid[end] = b'z';
+
+        Ok((grid, start, end))
+    }
+
+    fn part_one((grid, start, end): &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p1: u32 = search(grid, *start, *end).ok_or_else(no_path)?;
+
+        Ok(Solution::U32(p1))
+    }
+
+    fn part_two((grid, _, end): &Self::Input<'_>) -> Result<Solution, SolveError> {
+        let p2: u32 = grid
+            .find_all(&b'a')
+            .filter_map(|p| search(grid, p, *end))
+            .min()
+            .ok_or_else(no_path)?;
+
+        Ok(Solution::U32(p2))
+    }
+
+    /// A `size` by `size` heightmap that rises from S in the top left to E in the bottom
+    /// right, with random dips. The top row and right column are never lowered, so E can
+    /// always be reached.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(2);
+        let steps = 2 * (size - 1);
+        let rows = (0..size).map(|y| {
+            (0..size)
+                .map(|x| {
+                    let height = if steps >= 25 {
+                        (x + y) * 25 / steps
+                    } else {
+                        (x + y).min(25)
+                    };
+                    let dip = if y > 0 && x < size - 1 && rng.one_in(4) {
+                        rng.below(4) as usize
+                    } else {
+                        0
+                    };
+                    match (x, y) {
+                        (0, 0) => 'S',
+                        _ if (x, y) == (size - 1, size - 1) => 'E',
+                        _ => (b'a' + height.saturating_sub(dip) as u8) as char,
+                    }
+                })
+                .collect::<String>()
+        });
+        Some(rows.collect::<Vec<_>>().join("\n"))
+    }
 }
@@ -1,29 +1,66 @@
 use std::cmp::{max, Ordering};
 
 use itertools::Itertools;
-use serde_json::{Value, json};
+use serde_json::{json, Value};
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::SolveError,
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
 
-fn parse_signals(input: &str) -> Vec<Value> {
-    input
-        .lines()
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-            serde_json::from_str::<Value>(line).ok()
+pub const PUZZLE: Puzzle<Day13> = Puzzle::new(super::YEAR, 13, "Distress Signal");
+
+fn is_packet(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.is_u64(),
+        Value::Array(values) => values.iter().all(is_packet),
+        _ => false,
+    }
+}
+
+fn parse_signals(input: &str) -> Result<Vec<Value>, SolveError> {
+    input::lines(input)
+        .filter(|line| !line.is_empty())
+        .map(|line| match serde_json::from_str::<Value>(line) {
+            Ok(value) if is_packet(&value) => Ok(value),
+            _ => Err(SolveError::at(
+                input,
+                line,
+                "expected a packet of lists and integers",
+            )),
         })
-        .collect::<Vec<_>>()
+        .collect()
+}
+
+/// A list of up to three integers in `0..=max_value` and nested lists, at most three deep.
+fn random_packet(rng: &mut Rng, max_value: u64) -> Value {
+    fn list(rng: &mut Rng, max_value: u64, depth: u32) -> Value {
+        let items = (0..rng.below(4))
+            .map(|_| {
+                if depth < 3 && rng.one_in(3) {
+                    list(rng, max_value, depth + 1)
+                } else {
+                    json!(rng.below(max_value + 1))
+                }
+            })
+            .collect();
+        Value::Array(items)
+    }
+    list(rng, max_value, 0)
 }
 
-fn compare(a: &Value, b: &Value) -> Ordering {
+pub fn compare(a: &Value, b: &Value) -> Ordering {
     match (a, b) {
         (Value::Number(x), Value::Number(y)) => {
             let x = x.as_u64().unwrap();
             let y = &y.as_u64().unwrap();
             x.cmp(y)
-        },
+        }
         (Value::Array(left), Value::Array(right)) => {
             for i in 0..max(left.len(), right.len()) {
                 match (left.get(i), right.get(i)) {
@@ -37,36 +74,116 @@ fn compare(a: &Value, b: &Value) -> Ordering {
             }
             Ordering::Equal
         }
-        (Value::Array(_), Value::Number(_)) => {
-            compare(a, &Value::Array(vec![b.clone()]))
-        },
-        (Value::Number(_), Value::Array(_)) => {
-            compare(&Value::Array(vec![a.clone()]), b)
-        },
+        (Value::Array(_), Value::Number(_)) => compare(a, &Value::Array(vec![b.clone()])),
+        (Value::Number(_), Value::Array(_)) => compare(&Value::Array(vec![a.clone()]), b),
         _ => unreachable!(),
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut signals = parse_signals(input);
+pub struct Day13;
+
+impl Day for Day13 {
+    type Input<'a> = Vec<Value>;
+
+    fn parse(input: &str) -> Result<Vec<Value>, SolveError> {
+        parse_signals(input)
+    }
+
+    fn part_one(signals: &Vec<Value>) -> Result<Solution, SolveError> {
+        let p1 = signals
+            .iter()
+            .tuples()
+            .positions(|(a, b)| compare(a, b) != Ordering::Greater)
+            .map(|i| i + 1)
+            .sum();
+
+        Ok(Solution::USize(p1))
+    }
+
+    fn part_two(signals: &Vec<Value>) -> Result<Solution, SolveError> {
+        let beacons = [json!([[2]]), json!([[6]])];
+
+        let mut signals = signals.clone();
+        signals.extend(beacons.iter().cloned());
+        signals.sort_by(compare);
+
+        let p2 = signals
+            .iter()
+            .positions(|b| beacons.contains(b))
+            .map(|i| i + 1)
+            .product();
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` pairs of packets.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let pairs =
+            (0..size).map(|_| format!("{}\n{}", random_packet(rng, 10), random_packet(rng, 10)));
+        Some(pairs.collect::<Vec<_>>().join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
 
-    let p1 = signals
-        .iter()
-        .tuples()
-        .positions(|(a, b)| compare(a, b) != Ordering::Greater)
-        .map(|i| i + 1)
-        .sum();
+    use serde_json::{json, Value};
 
-    let beacons = [json!([[2]]), json!([[6]])];
+    use crate::{
+        days::day13::{compare, random_packet},
+        utils::{prop, rng::Rng},
+    };
 
-    signals.extend(beacons.iter().cloned());
-    signals.sort_by(compare);
-    
-    let p2 = signals
-        .iter()
-        .positions(|b| beacons.contains(b))
-        .map(|i| i + 1)
-        .product();
+    // Small values and shallow nesting make equal and prefix-related packets common.
+    fn packet(rng: &mut Rng) -> Value {
+        random_packet(rng, 3)
+    }
 
-    (Solution::USize(p1), Solution::USize(p2))
+    #[test]
+    fn compare_is_antisymmetric() {
+        prop::check(
+            |rng| (packet(rng), packet(rng)),
+            |(a, b)| compare(a, b) == compare(b, a).reverse() && compare(a, a) == Ordering::Equal,
+        );
+    }
+
+    #[test]
+    fn compare_is_transitive() {
+        prop::check(
+            |rng| (packet(rng), packet(rng), packet(rng)),
+            |(a, b, c)| {
+                let (ab, bc, ac) = (compare(a, b), compare(b, c), compare(a, c));
+                match (ab, bc) {
+                    (Ordering::Equal, _) => ac == bc,
+                    (_, Ordering::Equal) => ac == ab,
+                    _ if ab == bc => ac == ab,
+                    _ => true,
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn sorting_agrees_with_compare() {
+        prop::check(
+            |rng| (0..rng.below(10)).map(|_| packet(rng)).collect::<Vec<_>>(),
+            |packets| {
+                let mut sorted = packets.clone();
+                sorted.sort_by(compare);
+                sorted.iter().enumerate().all(|(i, a)| {
+                    sorted[i + 1..]
+                        .iter()
+                        .all(|b| compare(a, b) != Ordering::Greater)
+                })
+            },
+        );
+    }
+
+    #[test]
+    fn integers_compare_as_single_item_lists() {
+        assert_eq!(compare(&json!([[1], 2]), &json!([1, [2]])), Ordering::Equal);
+        assert_eq!(compare(&json!([[]]), &json!([0])), Ordering::Less);
+        assert_eq!(compare(&json!([3]), &json!([[2, 9]])), Ordering::Greater);
+    }
 }
@@ -1,64 +1,79 @@
-use crate::{Solution, SolutionPair};
+use std::ops::RangeInclusive;
+
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        params::{Param, Params},
+        solver::{Day, Puzzle},
+    },
+    utils::{
+        grid_store::{DenseGrid, GridStore, SparseGrid, StoreKind},
+        rng::Rng,
+        vector_2d::Vector2,
+    },
+    Solution,
+};
 use itertools::Itertools;
-use std::collections::HashMap;
 
-#[derive(PartialEq, Eq)]
-enum State {
+pub const PUZZLE: Puzzle<Day14> = Puzzle::new(super::YEAR, 14, "Regolith Reservoir");
+
+/// What fills a point of the cave. Points without one are air.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum State {
     Wall,
     Rest,
 }
 
-type Map = HashMap<(usize, usize), State>;
 type Position = (usize, usize);
 
-fn parse_position(p: &str) -> Position {
-    let (x1, y1) = p.split_once(',').unwrap();
+fn parse_position(input: &str, p: &str) -> Result<Position, SolveError> {
+    let (x1, y1) = error::split_once(input, p, ",")?;
 
-    (x1.parse().unwrap(), y1.parse().unwrap())
+    Ok((error::parse(input, x1)?, error::parse(input, y1)?))
 }
 
-fn add((x, y): Position, (dx, dy): (isize, isize)) -> Position {
-    let (x, y) = ((x as isize + dx), (y as isize + dy));
-    (x as usize, y as usize)
+fn parse_walls(input: &str, line: &str) -> Result<Vec<(Position, Position)>, SolveError> {
+    line.split(" -> ")
+        .tuple_windows()
+        .map(|(from, to)| Ok((parse_position(input, from)?, parse_position(input, to)?)))
+        .collect()
 }
 
-fn insert_walls(grid: &mut Map, line: &str) {
-    line.split(" -> ").tuple_windows().for_each(|(from, to)| {
-        let (x1, y1) = parse_position(from);
-        let (x2, y2) = parse_position(to);
-
-        for x in x1.min(x2)..=x1.max(x2) {
-            for y in y1.min(y2)..=y1.max(y2) {
-                grid.insert((x, y), State::Wall);
-            }
-        }
-    });
+/// Every point of rock the wall segments cover within `columns`.
+fn wall_points(
+    walls: &[(Position, Position)],
+    columns: RangeInclusive<usize>,
+) -> Result<Vec<Vector2>, SolveError> {
+    walls
+        .iter()
+        .flat_map(|&((x1, y1), (x2, y2))| {
+            let xs = x1.min(x2).max(*columns.start())..=x1.max(x2).min(*columns.end());
+            xs.cartesian_product(y1.min(y2)..=y1.max(y2))
+        })
+        .map(|point| Vector2::try_from(point).map_err(SolveError::new))
+        .collect()
 }
 
-fn map_anchors(grid: &Map) -> (usize, usize, usize, usize) {
-    grid.keys().fold(
-        (usize::MAX, usize::MAX, usize::MIN, usize::MIN),
-        |(min_x, min_y, max_x, max_y), (x, y)| {
-            (min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y))
-        },
-    )
+fn map_of<S: GridStore<State>>(cave: &Cave) -> Result<S, SolveError> {
+    let mut map = S::default();
+    for &wall in &cave.walls {
+        map.try_insert(wall, State::Wall)?;
+    }
+    Ok(map)
 }
 
-fn move_sand_to_rest(map: &Map, start: Position, depth: usize) -> Position {
+/// Where a grain from `start` comes to rest, or the first point on row `depth` it reaches.
+fn move_sand_to_rest(map: &impl GridStore<State>, start: Vector2, depth: usize) -> Vector2 {
     let mut current = start;
     let mut rest = None;
     let candidates = [(0isize, 1isize), (-1, 1), (1, 1)];
 
-    while current.1 < depth && rest.is_none() {
+    while current.row_index() < depth && rest.is_none() {
         let next = candidates
             .iter()
-            .find_map(|&d| {
-                let next = add(current, d);
-                match map.get(&next) {
-                    None => Some(next),
-                    _ => None,
-                }
-            });
+            .map(|&d| current + d)
+            .find(|&next| !map.contains(next));
 
         match next {
             Some(position) => current = position,
@@ -69,51 +84,197 @@ fn move_sand_to_rest(map: &Map, start: Position, depth: usize) -> Position {
     rest.unwrap_or(current)
 }
 
-fn part_one(mut map: Map, inlet: Position, depth: usize) -> (usize, Map) {
+fn fill_until_overflow<S: GridStore<State>>(cave: &Cave) -> Result<usize, SolveError> {
+    let (mut map, inlet, depth) = (map_of::<S>(cave)?, cave.inlet, cave.depth);
+    let mut rests = 0;
     loop {
         let pos = move_sand_to_rest(&map, inlet, depth);
-        if pos.1 == depth {
+        if pos.row_index() == depth {
+            break;
+        }
+        map.try_insert(pos, State::Rest)?;
+        rests += 1;
+        // A cave that catches all the sand blocks the inlet before any falls out.
+        if pos == inlet {
             break;
         }
-        map.insert(pos, State::Rest);
     }
 
-    let rests = map
-        .values()
-        .filter(|&s| *s == State::Rest)
-        .count();
-
-    (rests, map)
+    Ok(rests)
 }
 
-fn part_two(mut map: Map, inlet: Position, depth: usize) -> (usize, Map) {
+/// Sand rests on the floor at row `depth + 1` until it piles up to the inlet.
+fn fill_until_blocked<S: GridStore<State>>(cave: &Cave) -> Result<usize, SolveError> {
+    let (mut map, inlet, depth) = (map_of::<S>(cave)?, cave.inlet, cave.depth + 1);
+    let mut rests = 1;
     loop {
         let pos = move_sand_to_rest(&map, inlet, depth);
         match pos {
-            (500, 0) => break,
+            pos if pos == inlet => break,
             _ => {
-                map.insert(pos, State::Rest);
+                map.try_insert(pos, State::Rest)?;
+                rests += 1;
             }
         }
     }
 
-    let rests = map
-        .values()
-        .filter(|&s| *s == State::Rest)
-        .count() + 1;
+    Ok(rests)
+}
+
+const INLET: &str = "500,0";
 
-    (rests, map)
+/// The rock of the cave, put in the `GridStore` the `store` parameter picks for each part.
+pub struct Cave {
+    walls: Vec<Vector2>,
+    /// The row of the lowest rock.
+    depth: usize,
+    inlet: Vector2,
+    store: StoreKind,
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let inlet = (500, 0);
+pub struct Day14;
 
-    let mut map: Map = HashMap::new();
-    input.lines().for_each(|line| insert_walls(&mut map, line));
-    let (_, _, _, map_depth) = map_anchors(&map);
+impl Day for Day14 {
+    type Input<'a> = Cave;
 
-    let (p1, map) = part_one(map, inlet, map_depth);
-    let (p2, _map) = part_two(map, inlet, map_depth + 1);
+    const PARAMS: &'static [Param] = &[
+        Param::new("inlet", INLET, "x,y where the sand pours in"),
+        Param::new("store", "dense", "map of the cave: dense or sparse"),
+    ];
+
+    fn parse(input: &str) -> Result<Cave, SolveError> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Cave, SolveError> {
+        let inlet = params.text("inlet")?;
+        let (inlet_x, inlet_y) = parse_position(inlet, inlet).map_err(|_| {
+            SolveError::new(format!("parameter inlet expects x,y, not {:?}", inlet))
+        })?;
+
+        let walls = input::lines(input)
+            .map(|line| parse_walls(input, line))
+            .flatten_ok()
+            .collect::<Result<Vec<_>, _>>()?;
+        let depth = walls
+            .iter()
+            .flat_map(|&((_, y1), (_, y2))| [y1, y2])
+            .max()
+            .unwrap_or_default();
+
+        // Grains fall one row per step and move at most one column sideways, so no sand
+        // reaches rock further from the inlet than the floor two rows below the walls is deep.
+        let reach = depth.saturating_add(2);
+        let columns = inlet_x.saturating_sub(reach)..=inlet_x.saturating_add(reach);
+
+        Ok(Cave {
+            walls: wall_points(&walls, columns)?,
+            depth,
+            inlet: Vector2::try_from((inlet_x, inlet_y)).map_err(SolveError::new)?,
+            store: params.get("store")?,
+        })
+    }
+
+    fn part_one(cave: &Cave) -> Result<Solution, SolveError> {
+        let p1 = match cave.store {
+            StoreKind::Dense => fill_until_overflow::<DenseGrid<_>>(cave)?,
+            StoreKind::Sparse => fill_until_overflow::<SparseGrid<_>>(cave)?,
+        };
+
+        Ok(Solution::USize(p1))
+    }
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn part_two(cave: &Cave) -> Result<Solution, SolveError> {
+        let p2 = match cave.store {
+            StoreKind::Dense => fill_until_blocked::<DenseGrid<_>>(cave)?,
+            StoreKind::Sparse => fill_until_blocked::<SparseGrid<_>>(cave)?,
+        };
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` rock paths of one to four straight lines, around and below the inlet.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let width = 10 + size as i64;
+        let depth = 10 + size as i64 / 2;
+        let (x0, y0) = parse_position(INLET, INLET).ok()?;
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let paths = (0..size).map(|_| {
+            let mut point = (
+                rng.range((x0 - width).max(1)..=x0 + width),
+                rng.range(y0 + 2..=y0 + depth),
+            );
+            let mut path = vec![point];
+            for i in 0..rng.range(1..=4) {
+                let length = rng.range(1..=8) * *rng.pick(&[-1, 1]);
+                point = if i % 2 == 0 {
+                    ((point.0 + length).max(1), point.1)
+                } else {
+                    (point.0, (point.1 + length).max(y0 + 1))
+                };
+                path.push(point);
+            }
+            path.iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .join(" -> ")
+        });
+        Some(paths.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::etc::{solver::Solver, Solution};
+
+    #[test]
+    fn solve() {
+        let input = include_str!("../../input/day14/test.txt");
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::USize(24));
+        assert_eq!(p2, Solution::USize(93));
+    }
+
+    #[test]
+    fn stores_agree() {
+        let input = include_str!("../../input/day14/test.txt");
+        let sparse = super::PUZZLE
+            .solve_with(input, &"store=sparse".parse().unwrap(), false)
+            .unwrap()
+            .0;
+
+        assert_eq!(sparse, super::PUZZLE.solve(input).unwrap());
+    }
+
+    /// A ledge under the inlet catches every grain, so the sand piles up until it blocks
+    /// the inlet without any falling into the abyss.
+    #[test]
+    fn sand_that_never_falls_out() {
+        let (p1, p2) = super::PUZZLE.solve("480,2 -> 520,2").unwrap();
+
+        assert_eq!(p1, Solution::USize(4));
+        assert_eq!(p2, Solution::USize(4));
+    }
+
+    /// Rock out of the sand's reach is never stored, however far away it is, while rock far
+    /// below the rest would make a dense map too large and is refused.
+    #[test]
+    fn far_away_rock() {
+        let input = include_str!("../../input/day14/test.txt");
+        let far = format!("{}\n90000000000,3 -> 90000000000,4", input.trim_end());
+        let sparse = "store=sparse".parse().unwrap();
+
+        let expected = (Solution::USize(24), Solution::USize(93));
+        assert_eq!(super::PUZZLE.solve(&far).unwrap(), expected);
+        assert_eq!(
+            super::PUZZLE.solve_with(&far, &sparse, false).unwrap().0,
+            expected
+        );
+
+        let error = super::PUZZLE
+            .solve("500,2 -> 501,2\n500,90000000000 -> 501,90000000000")
+            .unwrap_err();
+        assert!(error.message.contains("DenseGrid"), "{}", error);
+    }
 }
@@ -1,57 +1,66 @@
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        params::{Param, Params},
+        solver::{Day, Puzzle},
+    },
+    utils::vector_2d::Vector2i64,
+    Solution,
+};
 
-type Position = (i64, i64);
+pub const PUZZLE: Puzzle<Day15> = Puzzle::new(super::YEAR, 15, "Beacon Exclusion Zone");
+
+type Position = Vector2i64;
 
 #[derive(Debug)]
-struct Sensor {
+pub struct Sensor {
     position: Position,
     closest_beacon: Position,
     distance: i64,
 }
 
-fn manhattan_distance((x1, y1): Position, (x2, y2): Position) -> i64 {
-    (y2 - y1).abs() + (x2 - x1).abs()
-}
-
 impl Sensor {
-    fn parse(line: &str) -> Option<Sensor> {
-        let (sensor, beacon) = line.split_once(": ")?;
-        let position = Sensor::parse_sensor(sensor)?;
-        let beacon = Sensor::parse_beacon(beacon)?;
+    fn parse(input: &str, line: &str) -> Result<Sensor, SolveError> {
+        let (sensor, beacon) = error::split_once(input, line, ": ")?;
+        let position = Sensor::parse_sensor(input, sensor)?;
+        let beacon = Sensor::parse_beacon(input, beacon)?;
 
-        Some(Sensor {
+        Ok(Sensor {
             position,
             closest_beacon: beacon,
-            distance: manhattan_distance(position, beacon),
+            distance: position.distance_to(beacon) as i64,
         })
     }
 
-    fn parse_sensor(sensor: &str) -> Option<Position> {
-        let (x, y) = sensor.trim_start_matches("Sensor at ").split_once(", ")?;
-        let x = x.trim_start_matches("x=").parse().unwrap();
-        let y = y.trim_start_matches("y=").parse().unwrap();
+    fn parse_sensor(input: &str, sensor: &str) -> Result<Position, SolveError> {
+        let (x, y) = error::split_once(input, sensor.trim_start_matches("Sensor at "), ", ")?;
+        let x = error::parse(input, x.trim_start_matches("x="))?;
+        let y = error::parse(input, y.trim_start_matches("y="))?;
 
-        Some((x, y))
+        Ok(Position::new(x, y))
     }
 
-    fn parse_beacon(beacon: &str) -> Option<Position> {
-        let (x, y) = beacon
-            .trim_start_matches("closest beacon is at ")
-            .split_once(", ")?;
-        let x = x.trim_start_matches("x=").parse().unwrap();
-        let y = y.trim_start_matches("y=").parse().unwrap();
+    fn parse_beacon(input: &str, beacon: &str) -> Result<Position, SolveError> {
+        let (x, y) = error::split_once(
+            input,
+            beacon.trim_start_matches("closest beacon is at "),
+            ", ",
+        )?;
+        let x = error::parse(input, x.trim_start_matches("x="))?;
+        let y = error::parse(input, y.trim_start_matches("y="))?;
 
-        Some((x, y))
+        Ok(Position::new(x, y))
     }
 
     fn y_line_coverage(&self, y: i64) -> Option<(i64, i64)> {
-        let dy = (self.position.1 - y).abs();
+        let dy = (self.position.y - y).abs();
         if dy <= self.distance {
-            let x = self.position.0;
+            let x = self.position.x;
             let dx = self.distance - dy;
-            return Some(((x - dx), (x + dx)))
+            return Some(((x - dx), (x + dx)));
         }
         None
     }
@@ -60,16 +69,15 @@ impl Sensor {
         if self.closest_beacon == point {
             return false;
         }
-        self.distance >= manhattan_distance(self.position, point)
+        self.distance >= self.position.distance_to(point) as i64
     }
 }
 
-
-fn part_two(sensors: &[Sensor], size: i64) -> i64 {
+fn find_distress_beacon(sensors: &[Sensor], size: i64) -> Result<i64, SolveError> {
     sensors
         .iter()
         .find_map(|s| {
-            let (x, y) = s.position;
+            let Position { x, y } = s.position;
             let x_min = (x - s.distance - 1).max(0);
             let x_max = x.min(size);
 
@@ -77,14 +85,14 @@ fn part_two(sensors: &[Sensor], size: i64) -> i64 {
             (x_min..=x_max).zip(y..=size).find_map(|(x, y)| {
                 sensors
                     .iter()
-                    .all(|s| !s.is_inside_range((x, y)))
+                    .all(|s| !s.is_inside_range(Position::new(x, y)))
                     .then_some(x * 4000000 + y)
             })
         })
-        .unwrap()
+        .ok_or_else(|| SolveError::new("no position is out of range of every sensor"))
 }
 
-fn merge_range(mut acc: Vec<(i64, i64)>, (x1, x2): Position) -> Vec<(i64, i64)> {
+fn merge_range(mut acc: Vec<(i64, i64)>, (x1, x2): (i64, i64)) -> Vec<(i64, i64)> {
     if acc.is_empty() {
         acc.push((x1, x2));
         return acc;
@@ -101,22 +109,64 @@ fn merge_range(mut acc: Vec<(i64, i64)>, (x1, x2): Position) -> Vec<(i64, i64)>
     acc
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let input = input;
-    let y = 2_000_000;
-    let size = 4_000_000;
+pub struct Scan {
+    sensors: Vec<Sensor>,
+    /// The row part one counts the covered positions of.
+    row: i64,
+    /// Part two searches `0..=size` in both directions.
+    size: i64,
+}
 
-    let sensors = input.lines().filter_map(Sensor::parse).collect::<Vec<_>>();
+pub struct Day15;
+
+impl Day for Day15 {
+    type Input<'a> = Scan;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new(
+            "y",
+            "2000000",
+            "row that part one counts the covered positions of",
+        ),
+        Param::new(
+            "size",
+            "4000000",
+            "largest x and y part two searches for the beacon",
+        ),
+    ];
+
+    fn parse(input: &str) -> Result<Scan, SolveError> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMS))
+    }
 
-    let p1 = sensors
-        .iter()
-        .filter_map(|s| s.y_line_coverage(y))
-        .sorted()
-        .fold(Vec::new(), merge_range)
-        .iter()
-        .fold(0, |acc, (x1, x2)| acc + (x2 - x1));
+    fn parse_with(input: &str, params: &Params) -> Result<Scan, SolveError> {
+        let sensors = input::lines(input)
+            .map(|line| Sensor::parse(input, line))
+            .collect::<Result<_, _>>()?;
 
-    let p2 = part_two(&sensors, size);
+        Ok(Scan {
+            sensors,
+            row: params.get("y")?,
+            size: params.get("size")?,
+        })
+    }
 
-    (Solution::I64(p1), Solution::I64(p2))
+    fn part_one(scan: &Scan) -> Result<Solution, SolveError> {
+        let p1 = scan
+            .sensors
+            .iter()
+            .filter_map(|s| s.y_line_coverage(scan.row))
+            .sorted()
+            .fold(Vec::new(), merge_range)
+            .iter()
+            .fold(0, |acc, (x1, x2)| acc + (x2 - x1));
+
+        Ok(Solution::I64(p1))
+    }
+
+    fn part_two(scan: &Scan) -> Result<Solution, SolveError> {
+        let p2 = find_distress_beacon(&scan.sensors, scan.size)?;
+
+        Ok(Solution::I64(p2))
+    }
 }
@@ -2,12 +2,23 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        params::{Param, Params},
+        solver::{Day, Puzzle},
+        stats::{NoStats, Stats},
+    },
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day16> = Puzzle::new(super::YEAR, 16, "Proboscidea Volcanium");
 
 const ALL: u64 = !0 << 2;
 
 #[derive(Debug)]
-struct Valve {
+pub struct Valve {
     id: u64,
     name: String,
     flow: usize,
@@ -15,37 +26,56 @@ struct Valve {
 }
 
 impl Valve {
-    fn parse(id: usize, line: &str) -> Valve {
+    fn parse(input: &str, id: usize, line: &str) -> Result<Valve, SolveError> {
+        if id >= u64::BITS as usize {
+            return Err(SolveError::at(
+                input,
+                line,
+                "too many valves to track in a bit mask",
+            ));
+        }
         let id = 1 << id;
-        let (valve, tunnels) = line.split_once(';').unwrap();
-        let (valve, rate) = valve.split_once('=').unwrap();
+        let (valve, tunnels) = error::split_once(input, line, ";")?;
+        let (valve, rate) = error::split_once(input, valve, "=")?;
         let tunnels = tunnels.split(", ").map(|s| s.to_owned()).collect_vec();
 
         let name = valve.to_owned();
-        let flow = rate.parse().unwrap();
-        Valve {
+        let flow = error::parse(input, rate)?;
+        Ok(Valve {
             id,
             name,
             flow,
             tunnels,
-        }
+        })
     }
 }
 
-struct TunnelMap {
+pub struct TunnelMap {
     map: HashMap<String, Valve>,
+    start: String,
+    minutes: usize,
 }
 
 impl TunnelMap {
-    fn max_pressure(&self, start: &str, minutes: usize) -> usize {
+    fn max_pressure(&self, start: &str, minutes: usize, stats: &mut impl Stats) -> usize {
         let mut cache = HashMap::new();
 
-        self.dfs(&mut cache, start, 0, minutes)
+        let best = self.dfs(&mut cache, stats, start, 0, minutes);
+
+        let states = stats.counter("states explored");
+        if states > 0 {
+            stats.gauge(
+                "cache hit rate",
+                stats.counter("cache hits") as f64 / states as f64,
+            );
+        }
+        best
     }
 
     fn dfs(
         &self,
         cache: &mut HashMap<(u64, u64, usize), usize>,
+        stats: &mut impl Stats,
         valve_key: &str,
         valves_open: u64,
         minutes_remaining: usize,
@@ -60,13 +90,16 @@ impl TunnelMap {
         let valve = self.map.get(valve_key).unwrap();
         let cache_key = (valve.id, valves_open, minutes_remaining);
 
+        stats.count("states explored", 1);
         if let Some(v) = cache.get(&cache_key) {
+            stats.count("cache hits", 1);
             return *v;
         }
 
-        let best_unopened = valve.tunnels
+        let best_unopened = valve
+            .tunnels
             .iter()
-            .map(|tunnel| self.dfs(cache, tunnel, valves_open, minutes_remaining - 1))
+            .map(|tunnel| self.dfs(cache, stats, tunnel, valves_open, minutes_remaining - 1))
             .max()
             .unwrap();
 
@@ -74,7 +107,13 @@ impl TunnelMap {
             true => best_unopened,
             _ => {
                 let minutes_remaining = minutes_remaining - 1;
-                let best_tunnel = self.dfs(cache, valve_key, open_valve(valves_open, valve.id), minutes_remaining);
+                let best_tunnel = self.dfs(
+                    cache,
+                    stats,
+                    valve_key,
+                    open_valve(valves_open, valve.id),
+                    minutes_remaining,
+                );
 
                 let value = valve.flow * minutes_remaining;
                 best_unopened.max(value + best_tunnel)
@@ -94,11 +133,6 @@ fn open_valve(mask: u64, bit: u64) -> u64 {
     mask | bit
 }
 
-fn part_one(map: HashMap<String, Valve>, start: &str, minutes: usize) -> usize {
-    let tunnel_map = TunnelMap { map };
-    tunnel_map.max_pressure(start, minutes)
-}
-
 fn trim_input(input: &str) -> String {
     input
         .replace("valves", "valve")
@@ -109,26 +143,74 @@ fn trim_input(input: &str) -> String {
         .replace(" has flow rate", "")
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let input = trim_input(input);
+pub struct Day16;
 
-    let valves = input
-        .lines()
-        .sorted()
-        .enumerate()
-        .map(|(i, l)| Valve::parse(i, l))
-        .map(|v| (v.name.clone(), v))
-        .collect::<HashMap<_, _>>();
+impl Day for Day16 {
+    type Input<'a> = TunnelMap;
 
-    let p1 = part_one(valves, "AA", 30);
-    let p2: u64 = 0;
+    const PARAMS: &'static [Param] = &[
+        Param::new("start", "AA", "valve you start at"),
+        Param::new("minutes", "30", "minutes until the volcano erupts"),
+    ];
 
-    (Solution::USize(p1), Solution::U64(p2))
+    fn parse(input: &str) -> Result<TunnelMap, SolveError> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<TunnelMap, SolveError> {
+        let start = params.text("start")?.to_owned();
+        let minutes = params.get("minutes")?;
+
+        // `trim_input` keeps every line on its own row, so errors still point at the right line
+        let input = trim_input(input);
+
+        let valves = input::lines(&input)
+            .sorted()
+            .enumerate()
+            .map(|(i, l)| Valve::parse(&input, i, l))
+            .map_ok(|v| (v.name.clone(), v))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        if !valves.contains_key(&start) {
+            return Err(SolveError::new(format!("no valve {} to start from", start)));
+        }
+        for valve in valves.values() {
+            if let Some(tunnel) = valve.tunnels.iter().find(|t| !valves.contains_key(*t)) {
+                return Err(SolveError::new(format!(
+                    "valve {} leads to unknown valve {:?}",
+                    valve.name, tunnel
+                )));
+            }
+        }
+
+        Ok(TunnelMap {
+            map: valves,
+            start,
+            minutes,
+        })
+    }
+
+    fn part_one(tunnel_map: &TunnelMap) -> Result<Solution, SolveError> {
+        Self::part_one_stats(tunnel_map, &mut NoStats)
+    }
+
+    fn part_one_stats(
+        tunnel_map: &TunnelMap,
+        stats: &mut impl Stats,
+    ) -> Result<Solution, SolveError> {
+        let p1 = tunnel_map.max_pressure(&tunnel_map.start, tunnel_map.minutes, stats);
+
+        Ok(Solution::USize(p1))
+    }
+
+    fn part_two(_tunnel_map: &TunnelMap) -> Result<Solution, SolveError> {
+        Ok(Solution::Unsolved)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::etc::Solution;
+    use crate::etc::{solver::Solver, Solution};
 
     use super::is_opened;
     use super::open_valve;
@@ -136,10 +218,8 @@ mod tests {
     #[test]
     fn test_day() {
         let input = include_str!("../../input/day16/test.txt");
-        let (p1, _) = super::solve(input);
-        if let Solution::U32(p1) = p1 {
-            assert_eq!(p1, 1651);
-        }
+        let (p1, _) = super::PUZZLE.solve(input).unwrap();
+        assert_eq!(p1, Solution::USize(1651));
     }
 
     #[test]
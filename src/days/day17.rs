@@ -1,8 +1,22 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use crate::{
+    etc::{
+        error::SolveError,
+        params::{Param, Params},
+        solver::{Day, Puzzle},
+    },
+    utils::{
+        grid_store::{DenseGrid, GridStore, SparseGrid, StoreKind},
+        rng::Rng,
+        vector_2d::Vector2,
+    },
+    Solution,
+};
 
-use crate::{utils::vector_2d::Vector2, Solution, SolutionPair};
+pub const PUZZLE: Puzzle<Day17> = Puzzle::new(super::YEAR, 17, "Pyroclastic Flow");
+
+const WIDTH: usize = 7;
 
 // chars:
 const VOID: u8 = b'.';
@@ -46,27 +60,17 @@ struct Rock {
     position: Vector2,
 }
 
-struct Map {
-    map: HashMap<Vector2, u8>,
+/// The chamber, `WIDTH` columns wide with row 0 at the floor, and the rock settled in it.
+#[derive(Default)]
+struct Map<S> {
+    map: S,
     rocks: usize,
     height: usize,
-    width: usize,
-}
-
-impl Default for Map {
-    fn default() -> Self {
-        Self {
-            map: Default::default(),
-            height: Default::default(),
-            width: 7,
-            rocks: 0,
-        }
-    }
 }
 
 type Shape = [[u8; 4]; 4];
 
-impl Map {
+impl<S: GridStore<()>> Map<S> {
     fn try_fit(&self, rock: &Rock, destination: &Vector2) -> bool {
         if destination.y < 0 {
             return false;
@@ -77,10 +81,10 @@ impl Map {
         for (dy, row) in rock.shape.iter().rev().enumerate() {
             for (dx, _) in row.iter().enumerate().filter(|(_, &c)| c == ROCK) {
                 let pixel = *destination + (dx, dy);
-                if self.width <= pixel.x as usize {
+                if WIDTH <= pixel.x as usize {
                     return false;
                 }
-                if let Some(_v) = self.map.get(&pixel) {
+                if self.map.contains(pixel) {
                     return false;
                 }
             }
@@ -97,38 +101,26 @@ impl Map {
         false
     }
 
-    fn place_rock(&mut self, rock: &Rock) {
+    fn place_rock(&mut self, rock: &Rock) -> Result<(), SolveError> {
         let mut max = self.height;
         for (y, row) in rock.shape.iter().rev().enumerate() {
-            for (x, v) in row.iter().enumerate().filter(|(_, &c)| c == ROCK) {
+            for (x, _) in row.iter().enumerate().filter(|(_, &c)| c == ROCK) {
                 let position = rock.position + Vector2::try_from((x, y)).unwrap();
-                self.map.insert(position, *v);
+                self.map.try_insert(position, ())?;
                 max = max.max(position.y as usize + 1);
             }
         }
         self.height = max;
         self.rocks += 1;
-    }
-
-    fn print(&self) {
-        for y in (0..=self.height).rev() {
-            for x in 0..self.width {
-                match self.map.get(&Vector2::try_from((x, y)).unwrap()) {
-                    Some(_) => print!("#"),
-                    None => print!("."),
-                }
-            }
-            println!()
-        }
-        println!()
+        Ok(())
     }
 }
 
-fn simulate(jets: &[isize], iterations: usize) -> usize {
+fn simulate<S: GridStore<()>>(jets: &[isize], iterations: usize) -> Result<usize, SolveError> {
     let mut jet_cycle = jets.iter().enumerate().cycle();
     let mut rock_cycle = ROCK_FORMATIONS.iter().enumerate().cycle();
     let mut seen: HashMap<(usize, usize), (usize, usize, usize)> = HashMap::new();
-    let mut map = Map::default();
+    let mut map = Map::<S>::default();
     let mut cycle_height = None;
     let mut iteration = 0;
 
@@ -148,7 +140,7 @@ fn simulate(jets: &[isize], iterations: usize) -> usize {
 
             map.try_move_rock(&mut rock, (*dx, 0));
             if !map.try_move_rock(&mut rock, (0, -1)) {
-                map.place_rock(&rock);
+                map.place_rock(&rock)?;
                 break;
             }
             jet_index = _jet_index;
@@ -177,33 +169,106 @@ fn simulate(jets: &[isize], iterations: usize) -> usize {
         }
     }
 
-    map.height + cycle_height.unwrap_or_default()
+    Ok(map.height + cycle_height.unwrap_or_default())
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let jets = input
-        .as_bytes()
-        .iter()
-        .map(|b| match b {
-            b'>' => 1,
-            b'<' => -1,
-            _ => unreachable!(),
+pub struct Jets {
+    pushes: Vec<isize>,
+    /// How many rocks fall in each part.
+    rocks: [usize; 2],
+    store: StoreKind,
+}
+
+impl Jets {
+    fn simulate(&self, rocks: usize) -> Result<usize, SolveError> {
+        match self.store {
+            StoreKind::Dense => simulate::<DenseGrid<_>>(&self.pushes, rocks),
+            StoreKind::Sparse => simulate::<SparseGrid<_>>(&self.pushes, rocks),
+        }
+    }
+}
+
+pub struct Day17;
+
+impl Day for Day17 {
+    type Input<'a> = Jets;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new("rocks_one", "2022", "rocks that fall in part one"),
+        Param::new("rocks_two", "1000000000000", "rocks that fall in part two"),
+        Param::new("store", "dense", "map of the tower: dense or sparse"),
+    ];
+
+    fn parse(input: &str) -> Result<Jets, SolveError> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Jets, SolveError> {
+        let pushes = input
+            .trim_end()
+            .char_indices()
+            .map(|(i, c)| match c {
+                '>' => Ok(1),
+                '<' => Ok(-1),
+                _ => Err(SolveError::at(
+                    input,
+                    &input[i..i + c.len_utf8()],
+                    "expected < or >",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if pushes.is_empty() {
+            return Err(SolveError::new("expected a jet pattern"));
+        }
+
+        Ok(Jets {
+            pushes,
+            rocks: [params.get("rocks_one")?, params.get("rocks_two")?],
+            store: params.get("store")?,
         })
-        .collect_vec();
+    }
+
+    fn part_one(jets: &Jets) -> Result<Solution, SolveError> {
+        let p1 = jets.simulate(jets.rocks[0])?;
+
+        Ok(Solution::USize(p1))
+    }
 
-    let p1 = simulate(&jets, 2022);
-    let p2 = simulate(&jets, 1_000_000_000_000);
+    fn part_two(jets: &Jets) -> Result<Solution, SolveError> {
+        let p2 = jets.simulate(jets.rocks[1])?;
 
-    // missing 46..
-    (Solution::USize(p1), Solution::USize(p2))
+        // missing 46..
+        Ok(Solution::USize(p2))
+    }
+
+    /// A jet pattern of `size` pushes.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some((0..size.max(1)).map(|_| *rng.pick(&['<', '>'])).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::{solver::Solver, Solution};
 
     #[test]
     fn solve() {
         let input = include_str!("../../input/day17/test.txt");
-        _ = super::solve(input);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::USize(3068));
+        assert_eq!(p2, Solution::USize(1514285714288));
+    }
+
+    #[test]
+    fn stores_agree() {
+        let input = include_str!("../../input/day17/test.txt");
+        let sparse = super::PUZZLE
+            .solve_with(input, &"store=sparse".parse().unwrap(), false)
+            .unwrap()
+            .0;
+
+        assert_eq!(sparse, super::PUZZLE.solve(input).unwrap());
     }
 }
@@ -1,126 +1,147 @@
-use std::{collections::HashSet, ops::Add};
-
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
-
-const DIRS: [(i32, i32, i32); 6] = [
-    (-1, 0, 0),
-    (1, 0, 0),
-    (0, -1, 0),
-    (0, 1, 0),
-    (0, 0, -1),
-    (0, 0, 1),
-];
-
-#[derive(Hash, Eq, PartialEq, Clone, Copy)]
-struct Position(i32, i32, i32);
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::{cuboid::Cuboid, rng::Rng, vector_3d::Vector3i32, voxels::Voxels},
+    Solution,
+};
 
-impl Add<(i32, i32, i32)> for Position {
-    type Output = Position;
+pub const PUZZLE: Puzzle<Day18> = Puzzle::new(super::YEAR, 18, "Boiling Boulders");
 
-    fn add(self, (dx, dy, dz): (i32, i32, i32)) -> Self::Output {
-        let Position(x, y, z) = self;
-        Position(x + dx, y + dy, z + dz)
-    }
-}
+pub type Position = Vector3i32;
 
-struct GridSize {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Default for GridSize {
-    fn default() -> Self {
-        Self {
-            x: i32::MIN,
-            y: i32::MIN,
-            z: i32::MIN,
-        }
-    }
-}
-
-fn part_one(boxes: &HashSet<Position>) -> usize {
+fn surface_area(boxes: &Voxels<i32>) -> usize {
     boxes
         .iter()
-        .map(|Position(x, y, z)| {
-            let mut sides = 6;
-            for (dx, dy, dz) in DIRS {
-                let check = Position(x + dx, y + dy, z + dz);
-                if boxes.contains(&check) {
-                    sides -= 1;
-                }
-            }
-            sides
+        .map(|cube| {
+            cube.adjacent_points()
+                .iter()
+                .filter(|&&side| !boxes.contains(side))
+                .count()
         })
         .sum()
 }
 
-fn bounds_check(Position(x, y, z): &Position, bounds: &GridSize) -> bool {
-    let (x2, y2, z2) = (bounds.x + 1, bounds.y + 1, bounds.z + 1);
-
-    (-1..=x2).contains(x) && (-1..=y2).contains(y) && (-1..=z2).contains(z)
-}
-
-fn part_two(boxes: &HashSet<Position>, bounds: GridSize) -> usize {
-    let mut visited = HashSet::new();
-    let mut queue = Vec::new();
-    queue.push(Position(0, 0, 0));
+/// Floods the air around the droplet from a corner of its bounds, which leave a layer
+/// of air on every side so the water can reach all of them, and counts the faces the
+/// water touches.
+fn exterior_surface_area(boxes: &Voxels<i32>) -> usize {
+    let bounds = *boxes.bounds();
+    let mut visited = Voxels::new(bounds);
+    let mut queue = vec![bounds.min];
     let mut total = 0;
 
     while let Some(position) = queue.pop() {
-        if visited.contains(&position) {
+        if !visited.insert(position) {
             continue;
         }
 
-        for dir in DIRS.iter() {
-            let side = position.add(*dir);
-            if boxes.contains(&side) {
+        for side in position.adjacent_points() {
+            if boxes.contains(side) {
                 total += 1;
-            } else if bounds_check(&side, &bounds) {
+            } else if bounds.contains(side) && !visited.contains(side) {
                 queue.push(side);
             }
         }
-
-        visited.insert(position);
     }
     total
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut grid_size = GridSize::default();
+pub struct Day18;
+
+impl Day for Day18 {
+    type Input<'a> = Voxels<i32>;
+
+    fn parse(input: &str) -> Result<Voxels<i32>, SolveError> {
+        let cubes = input::lines(input)
+            .map(|l| {
+                l.split(',')
+                    .map(|x| error::parse::<i32>(input, x))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .collect_tuple()
+                    .map(|(x, y, z)| Position::new(x, y, z))
+                    .ok_or_else(|| SolveError::at(input, l, "expected x,y,z"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The droplet with a layer of air around it, for `exterior_surface_area`, which also
+        // looks one point past that layer.
+        let bounds = Cuboid::from_points(cubes.iter().copied())
+            .unwrap_or(Cuboid::new(Position::default(), Position::default()))
+            .expand(1)
+            .filter(|air| air.expand(1).is_some())
+            .and_then(Voxels::try_new);
+        let mut boxes = bounds.ok_or_else(|| {
+            SolveError::new("the cubes and the air around them span too much space to flood")
+        })?;
+        for cube in cubes {
+            boxes.insert(cube);
+        }
+
+        Ok(boxes)
+    }
 
-    let boxes = input
-        .lines()
-        .map(|l| {
-            l.split(',')
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect_tuple()
-                .unwrap()
-        })
-        .map(|(x, y, z)| Position(x, y, z))
-        .map(|Position(x, y, z)| {
-            grid_size.x = grid_size.x.max(x);
-            grid_size.y = grid_size.y.max(y);
-            grid_size.z = grid_size.z.max(z);
-            Position(x, y, z)
-        })
-        .collect::<HashSet<_>>();
+    fn part_one(boxes: &Voxels<i32>) -> Result<Solution, SolveError> {
+        let p1 = surface_area(boxes);
 
-    let p1 = part_one(&boxes);
-    let p2 = part_two(&boxes, grid_size);
+        Ok(Solution::USize(p1))
+    }
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn part_two(boxes: &Voxels<i32>) -> Result<Solution, SolveError> {
+        let p2 = exterior_surface_area(boxes);
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` distinct cubes packed into the smallest cube of space that holds twice as many.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let side = (1..).find(|side| side * side * side >= 2 * size).unwrap();
+        let cubes = rng.sample(side * side * side, size).into_iter().map(|i| {
+            // Coordinates start at 1, like those of the puzzle input.
+            format!(
+                "{},{},{}",
+                i % side + 1,
+                i / side % side + 1,
+                i / side / side + 1
+            )
+        });
+        Some(cubes.collect::<Vec<_>>().join("\n"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::{solver::Solver, Solution};
 
     #[test]
     fn solve() {
         let input = include_str!("../../input/day18/test.txt");
-        let (p1, p2) = super::solve(input);
-        println!("{}, {}", p1, p2);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::USize(64));
+        assert_eq!(p2, Solution::USize(58));
+    }
+
+    #[test]
+    fn huge_spaces_are_refused() {
+        let error = super::PUZZLE
+            .solve("1,1,1\n100000,100000,100000")
+            .unwrap_err();
+
+        assert!(error.message.contains("too much space"), "{}", error);
+
+        for input in ["2147483647,1,1", "2147483646,1,1", "-2147483647,1,1"] {
+            let error = super::PUZZLE.solve(input).unwrap_err();
+            assert!(error.message.contains("too much space"), "{}", error);
+        }
+        for input in ["2147483645,1,1", "-2147483646,1,1"] {
+            let near = super::PUZZLE.solve(input).unwrap();
+            assert_eq!(near, (Solution::USize(6), Solution::USize(6)));
+        }
     }
 }
@@ -1,122 +1,90 @@
-use std::collections::HashMap;
-
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    Solution,
+};
 use itertools::Itertools;
 
-struct Blueprint {
-    ore: u8,
-    clay: u8,
-    obsidian: (u8, u8),
-    geode: (u8, u8),
-}
-
-enum Resource {
-    Ore,
-    Clay,
-    Obsidian,
-    Geode,
-}
-
-struct State {
-    ores: [u8; 4],
-    robots: [u8; 4],
-}
-
-impl Default for State {
-    fn default() -> Self {
-        Self {
-            ores: [1, 0, 0, 0],
-            robots: Default::default(),
-        }
-    }
-}
-
-impl State {
-    fn _tick(&mut self) {
-        for (i, robot) in self.robots.iter().enumerate() {
-            self.ores[i] += robot;
-        }
-    }
-
-    fn produce_geodes(&self, blueprint: &Blueprint) -> u32 {
-        let mut cache = HashMap::new();
-        self.try_options(blueprint, &mut cache, 24)
-    }
-
-    fn try_options(
-        &self,
-        blueprint: &Blueprint,
-        _cache: &mut HashMap<(State, u8), u32>,
-        minutes: u8,
-    ) -> u32 {
-        if minutes == 0 {
-            return 0;
-        }
-        if self.try_buy(blueprint, Resource::Geode) {}
-        0
-    }
-
-    fn try_buy(&self, blueprint: &Blueprint, resource: Resource) -> bool {
-        if self.can_afford(blueprint, resource) {
-            return true;
-        }
-        false
-    }
+pub const PUZZLE: Puzzle<Day19> = Puzzle::new(super::YEAR, 19, "Not Enough Minerals");
 
-    fn can_afford(&self, blueprint: &Blueprint, resource: Resource) -> bool {
-        match resource {
-            Resource::Ore => self.ores[0] >= blueprint.ore,
-            Resource::Clay => self.ores[1] >= blueprint.clay,
-            Resource::Obsidian => self.ores[0] > blueprint.ore && self.ores[1] >= blueprint.clay,
-            Resource::Geode => self.ores[0] > blueprint.ore && self.ores[2] > blueprint.geode.1,
-        }
-    }
+/// The costs of one blueprint's robots, in ore unless said otherwise.
+pub struct Blueprint {
+    pub ore: u8,
+    pub clay: u8,
+    /// Ore and clay.
+    pub obsidian: (u8, u8),
+    /// Ore and obsidian.
+    pub geode: (u8, u8),
 }
 
 impl Blueprint {
-    fn parse(line: &str) -> Blueprint {
-        let (_, rest) = line.split_once(':').unwrap();
-        rest.split('.');
+    fn parse(input: &str, line: &str) -> Result<Blueprint, SolveError> {
+        let (_, rest) = error::split_once(input, line, ":")?;
 
-        let (clay, ore, obs_ore, obs_clay, geode_ore, geode_obsidian) = rest
+        let (ore, clay, obs_ore, obs_clay, geode_ore, geode_obsidian) = rest
             .split(|c: char| !c.is_ascii_digit()) // split everything that isn't a digit
-            .filter_map(|w| w.parse::<u8>().ok())
+            .filter(|w| !w.is_empty())
+            .map(|w| error::parse::<u8>(input, w))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .collect_tuple()
-            .unwrap();
+            .ok_or_else(|| SolveError::at(input, line, "expected six robot costs"))?;
 
-        Blueprint {
-            clay,
+        Ok(Blueprint {
             ore,
+            clay,
             obsidian: (obs_ore, obs_clay),
             geode: (geode_ore, geode_obsidian),
-        }
+        })
     }
 }
 
-fn part_one(blueprints: &[Blueprint]) -> u32 {
-    blueprints
-        .iter()
-        .enumerate()
-        .map(|(i, bp)| (i + 1) as u32 * State::default().produce_geodes(bp))
-        .sum()
-}
+pub struct Day19;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let blueprints = input.lines().map(Blueprint::parse).collect_vec();
+impl Day for Day19 {
+    type Input<'a> = Vec<Blueprint>;
 
-    let p1 = part_one(&blueprints);
-    let p2: u64 = 0;
+    fn parse(input: &str) -> Result<Vec<Blueprint>, SolveError> {
+        input::lines(input)
+            .map(|line| Blueprint::parse(input, line))
+            .collect()
+    }
 
-    (Solution::U32(p1), Solution::U64(p2))
+    fn part_one(_blueprints: &Vec<Blueprint>) -> Result<Solution, SolveError> {
+        Ok(Solution::Unsolved)
+    }
+
+    fn part_two(_blueprints: &Vec<Blueprint>) -> Result<Solution, SolveError> {
+        Ok(Solution::Unsolved)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::etc::solver::Solver;
 
     #[test]
     fn test_solve() {
         let input = include_str!("../../input/day19/test.txt");
-        solve(input);
+        let (p1, p2) = PUZZLE.solve(input).unwrap();
+
+        assert_eq!((p1, p2), (Solution::Unsolved, Solution::Unsolved));
+    }
+
+    #[test]
+    fn parse_costs() {
+        let input = include_str!("../../input/day19/test.txt");
+        let blueprints = Day19::parse(input).unwrap();
+        let first = &blueprints[0];
+
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(
+            (first.ore, first.clay, first.obsidian, first.geode),
+            (4, 2, (3, 14), (2, 7))
+        );
     }
 }
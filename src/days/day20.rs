@@ -1,4 +1,14 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day20> = Puzzle::new(super::YEAR, 20, "Grove Positioning System");
 
 fn find_new_index(position_values: &[(usize, isize)], index: usize) -> usize {
     position_values
@@ -36,14 +46,53 @@ fn mix(original: &[isize], iterations: usize, decrypt_key: isize) -> isize {
         .sum()
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let values: Vec<isize> = input
-        .lines()
-        .map(|l| l.parse().unwrap())
-        .collect::<Vec<_>>();
+pub struct Day20;
+
+impl Day for Day20 {
+    type Input<'a> = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Vec<isize>, SolveError> {
+        let values: Vec<isize> = input::lines(input)
+            .map(|l| error::parse(input, l))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if values.len() < 2 || !values.contains(&0) {
+            return Err(SolveError::new(
+                "expected at least two numbers, one of them 0",
+            ));
+        }
+
+        Ok(values)
+    }
 
-    let p1 = mix(&values, 1, 1);
-    let p2 = mix(&values, 10, 811_589_153);
+    fn part_one(values: &Vec<isize>) -> Result<Solution, SolveError> {
+        let p1 = mix(values, 1, 1);
 
-    (Solution::ISize(p1), Solution::ISize(p2))
+        Ok(Solution::ISize(p1))
+    }
+
+    fn part_two(values: &Vec<isize>) -> Result<Solution, SolveError> {
+        let p2 = mix(values, 10, 811_589_153);
+
+        Ok(Solution::ISize(p2))
+    }
+
+    /// `size` numbers, exactly one of them 0.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(2);
+        let zero = rng.below(size as u64) as usize;
+        let numbers = (0..size).map(|i| match i == zero {
+            true => 0,
+            false => match rng.range(-10_000..=10_000) {
+                0 => 1,
+                n => n,
+            },
+        });
+        Some(
+            numbers
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
 }
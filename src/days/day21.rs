@@ -1,11 +1,20 @@
-use std::{collections::HashMap};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    etc::{
+        error::{self, SolveError},
+        input,
+        solver::{Day, Puzzle},
+    },
+    Solution,
+};
 
-#[derive(Debug)]
-enum Job<'a> {
+pub const PUZZLE: Puzzle<Day21> = Puzzle::new(super::YEAR, 21, "Monkey Math");
+
+#[derive(Clone, Debug)]
+pub enum Job<'a> {
     Number(i64),
     Math(&'a str, &'a str, &'a str),
 }
@@ -40,13 +49,17 @@ fn try_humn_value(map: &mut HashMap<&str, Job>, value: i64) -> i64 {
     unreachable!()
 }
 
-fn part_two(mut map: HashMap<&str, Job>) -> i64 {
+fn find_humn_value(mut map: HashMap<&str, Job>) -> i64 {
     let mut lower_bound: i64 = 0;
     let mut upper_bound: i64 = 0;
     let mut guess = 1;
     loop {
         let result = try_humn_value(&mut map, guess);
         if result == 0 {
+            // `/` rounds down, so a few neighbouring values can balance root too
+            while try_humn_value(&mut map, guess - 1) == 0 {
+                guess -= 1;
+            }
             return guess;
         }
 
@@ -64,23 +77,59 @@ fn part_two(mut map: HashMap<&str, Job>) -> i64 {
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let monkey_map = input
-        .lines()
-        .map(|l| {
-            let (m, r) = l.split_once(": ").unwrap();
-            match r.parse::<i64>() {
-                Ok(v) => (m, Job::Number(v)),
-                Err(_) => {
-                    let (l, o, r) = r.split(' ').collect_tuple().unwrap();
-                    (m, Job::Math(l, o, r))
+pub struct Day21;
+
+impl Day for Day21 {
+    type Input<'a> = HashMap<&'a str, Job<'a>>;
+
+    fn parse(input: &str) -> Result<HashMap<&str, Job<'_>>, SolveError> {
+        let monkey_map = input::lines(input)
+            .map(|l| {
+                let (m, r) = error::split_once(input, l, ": ")?;
+                match r.parse::<i64>() {
+                    Ok(v) => Ok((m, Job::Number(v))),
+                    Err(_) => match r.split(' ').collect_tuple() {
+                        Some((l, o @ ("+" | "-" | "*" | "/"), r)) => Ok((m, Job::Math(l, o, r))),
+                        _ => Err(SolveError::at(
+                            input,
+                            r,
+                            "expected a number or `<monkey> <op> <monkey>`",
+                        )),
+                    },
+                }
+            })
+            .collect::<Result<HashMap<_, _>, SolveError>>()?;
+
+        for (name, job) in &monkey_map {
+            if let Job::Math(l, _, r) = job {
+                if let Some(missing) = [l, r].into_iter().find(|m| !monkey_map.contains_key(*m)) {
+                    return Err(SolveError::new(format!(
+                        "{} waits for unknown monkey {}",
+                        name, missing
+                    )));
                 }
             }
-        })
-        .collect::<HashMap<_, _>>();
+        }
+        if !matches!(monkey_map.get("root"), Some(Job::Math(..)))
+            || !monkey_map.contains_key("humn")
+        {
+            return Err(SolveError::new(
+                "expected a root monkey doing math and a humn monkey",
+            ));
+        }
+
+        Ok(monkey_map)
+    }
 
-    let p1 = calc(&monkey_map, "root");
-    let p2 = part_two(monkey_map);
+    fn part_one(monkey_map: &HashMap<&str, Job<'_>>) -> Result<Solution, SolveError> {
+        let p1 = calc(monkey_map, "root");
 
-    (Solution::I64(p1), Solution::I64(p2))
+        Ok(Solution::I64(p1))
+    }
+
+    fn part_two(monkey_map: &HashMap<&str, Job<'_>>) -> Result<Solution, SolveError> {
+        let p2 = find_humn_value(monkey_map.clone());
+
+        Ok(Solution::I64(p2))
+    }
 }
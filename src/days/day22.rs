@@ -1,54 +1,61 @@
-use std::collections::HashMap;
-
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
-
-#[derive(Debug)]
-enum Tile {
+use crate::{
+    etc::{
+        error::SolveError,
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::{
+        grid::Grid,
+        vector_2d::{Direction, Vector2, YAxis},
+    },
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day22> = Puzzle::new(super::YEAR, 22, "Monkey Map");
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tile {
+    /// Off the map, where the path wraps around.
+    Void,
     Open,
     Wall,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Turn {
+pub enum Turn {
     L,
     R,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     Move(u32),
     Turn(Turn),
 }
 
-type Grove = HashMap<(usize, usize), Tile>;
+pub type Grove = Grid<Tile>;
 
-fn password(((column, row), (dx, dy)): ((usize, usize), (isize, isize))) -> usize {
-    let f = match (dx, dy) {
-        (1, 0) => 0,
-        (0, 1) => 1,
-        (-1, 0) => 2,
-        (0, -1) => 3,
+fn password((position, facing): (Vector2, Direction)) -> usize {
+    let f = match facing {
+        Direction::E => 0,
+        Direction::S => 1,
+        Direction::W => 2,
+        Direction::N => 3,
         _ => unreachable!(),
     };
-    row * 1000 + column * 4 + f
+    // Rows and columns of the password count from 1.
+    (position.row_index() + 1) * 1000 + (position.column_index() + 1) * 4 + f
 }
 
-fn parse_map(map: &str) -> Grove {
-    map.lines()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.bytes().enumerate().filter_map(move |(x, tile)| {
-                let position = (x + 1, y + 1);
-                match tile {
-                    b'.' => Some((position, Tile::Open)),
-                    b'#' => Some((position, Tile::Wall)),
-                    _ => None,
-                }
-            })
-        })
-        .collect()
+fn parse_map(map: &str) -> Result<Grove, SolveError> {
+    Grid::parse_padded(map, Tile::Void, |tile| match tile {
+        ' ' => Some(Tile::Void),
+        '.' => Some(Tile::Open),
+        '#' => Some(Tile::Wall),
+        _ => None,
+    })
 }
 
 fn parse_instructions(instructions: &str) -> Vec<Instruction> {
@@ -69,120 +76,96 @@ fn parse_instructions(instructions: &str) -> Vec<Instruction> {
     moves.interleave(turns).collect()
 }
 
-fn rotate_point(point: (isize, isize), turn: &Turn) -> (isize, isize) {
-    let angle = match turn {
-        Turn::L => -90.0,
-        Turn::R => 90.0,
-    };
-    let angle_radians = angle * std::f64::consts::PI / 180.0;
-    let rotation_matrix = [
-        [angle_radians.cos(), -angle_radians.sin()],
-        [angle_radians.sin(), angle_radians.cos()],
-    ];
-    let x = point.0 as f64;
-    let y = point.1 as f64;
-    let rotated_x = (rotation_matrix[0][0] * x + rotation_matrix[0][1] * y) as isize;
-    let rotated_y = (rotation_matrix[1][0] * x + rotation_matrix[1][1] * y) as isize;
-    (rotated_x, rotated_y)
+fn turn(facing: Direction, turn: &Turn) -> Direction {
+    match turn {
+        Turn::L => facing.turn_left(),
+        Turn::R => facing.turn_right(),
+    }
 }
 
+fn follow_path(map: &Grove, instructions: &[Instruction]) -> Result<usize, SolveError> {
+    fn add_with_wrap(map: &Grove, position: Vector2, facing: Direction) -> Vector2 {
+        let (width, height) = (map.width() as isize, map.height() as isize);
+        let next = position + facing.unit(YAxis::Down);
 
-fn part_one(map: &Grove, instructions: &[Instruction]) -> usize {
-    fn add_with_wrap(grid_size: (usize, usize, usize, usize), (x, y): (usize, usize), (dx, dy): (isize, isize)) -> (usize, usize) {
-        let (mut x, mut y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-    
-        if x < grid_size.0 {
-            x = grid_size.1;
-        }
-        if grid_size.1 < x {
-            x = grid_size.0;
-        }
-        if y < grid_size.2 {
-            y = grid_size.3
-        }
-        if grid_size.3 < y {
-            y = grid_size.2
+        Vector2 {
+            x: next.x.rem_euclid(width),
+            y: next.y.rem_euclid(height),
         }
-    
-    
-        (x,y)
     }
-    let x = (1..usize::MAX)
-        .find(|x| map.contains_key(&(*x, 1)))
-        .unwrap();
 
-    let map_size = map.keys().fold(
-        (usize::MAX, usize::MIN, usize::MAX, usize::MIN),
-        |(x1, x2, y1, y2), &(x, y)| (x1.min(x), x2.max(x), y1.min(y), y2.max(y)),
-    );
-
-    let y: usize = 1;
+    let start = map
+        .iter()
+        .take(map.width())
+        .find(|(_, tile)| **tile != Tile::Void)
+        .map(|(position, _)| position)
+        .ok_or_else(|| SolveError::new("the top row of the map has no tiles"))?;
 
     let final_state =
         instructions
             .iter()
             .fold(
-                ((x, y), (1, 0)),
-                |((x, y), (dx, dy)), instruction| match instruction {
-                    Instruction::Turn(t) => ((x, y), rotate_point((dx, dy), t)),
+                (start, Direction::E),
+                |(position, facing), instruction| match instruction {
+                    Instruction::Turn(t) => (position, turn(facing, t)),
                     Instruction::Move(steps) => {
-                        let mut position = (x, y);
+                        let mut position = position;
                         for _ in 1..=*steps {
-                            let mut next = add_with_wrap(map_size, position, (dx, dy));
+                            let mut next = add_with_wrap(map, position, facing);
 
-                            while map.get(&next).is_none() {
-                                next = add_with_wrap(map_size, next, (dx, dy))
+                            while map[next] == Tile::Void {
+                                next = add_with_wrap(map, next, facing)
                             }
 
-                            match map.get(&next) {
-                                Some(Tile::Open) => position = next,
-                                Some(Tile::Wall) => break,
-                                _ => unreachable!(),
+                            match map[next] {
+                                Tile::Open => position = next,
+                                Tile::Wall => break,
+                                Tile::Void => unreachable!(),
                             }
                         }
-                        (position, (dx, dy))
+                        (position, facing)
                     }
                 },
             );
 
-    password(final_state)
+    Ok(password(final_state))
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (map, instructions) = input.split_once("\r\n\r\n").unwrap();
-    let map = parse_map(map);
-    let instructions = parse_instructions(instructions);
-
-    for y in 1..=12 {
-        for x in 1..=16 {
-            match map.get(&(x, y)) {
-                Some(Tile::Open) => print!("."),
-                Some(Tile::Wall) => print!("#"),
-                None => print!(" "),
-            }
-        }
-        println!()
+pub struct Day22;
+
+impl Day for Day22 {
+    type Input<'a> = (Grove, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<(Grove, Vec<Instruction>), SolveError> {
+        let (map, instructions) = input::two_blocks(input)
+            .ok_or_else(|| SolveError::new("expected a blank line between the map and the path"))?;
+        let map = parse_map(map)?;
+        let instructions = parse_instructions(instructions);
+
+        Ok((map, instructions))
     }
 
-    println!("{:?}", instructions);
+    fn part_one((map, instructions): &(Grove, Vec<Instruction>)) -> Result<Solution, SolveError> {
+        let p1 = follow_path(map, instructions)?;
 
-    let p1 = part_one(&map, &instructions);
-    let p2 = 0;
+        Ok(Solution::USize(p1))
+    }
 
-    (Solution::USize(p1), Solution::U64(p2))
+    fn part_two(_: &(Grove, Vec<Instruction>)) -> Result<Solution, SolveError> {
+        Ok(Solution::Unsolved)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::etc::Solution;
+    use crate::etc::{solver::Solver, Solution};
 
     #[test]
     fn solve() {
         let input = include_str!("../../input/day22/test.txt");
-        let (p1, _p2) = super::solve(input);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
 
-        if let Solution::U32(v) = p1 {
-            assert_eq!(v, 6032)
-        }
+        assert_eq!(p1, Solution::USize(6032));
+        assert_eq!(p2, Solution::Unsolved);
     }
 }
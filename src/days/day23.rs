@@ -1,48 +1,38 @@
-use crate::{Solution, SolutionPair};
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    ops::Add,
+use crate::{
+    etc::{
+        error::SolveError,
+        params::{Param, Params},
+        solver::{Day, Puzzle},
+        stats::{NoStats, Stats},
+    },
+    utils::{
+        grid::Grid,
+        grid_store::{DenseGrid, GridStore, SparseGrid, StoreKind},
+        rng::Rng,
+        vector_2d::{Direction, Vector2, YAxis},
+    },
+    Solution,
 };
+use std::collections::VecDeque;
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone, PartialOrd, Debug, Ord)]
-struct Point(isize, isize);
+pub const PUZZLE: Puzzle<Day23> = Puzzle::new(super::YEAR, 23, "Unstable Diffusion");
 
-impl Add<Point> for Point {
-    type Output = Point;
+type Point = Vector2;
 
-    fn add(self, Point(dx, dy): Point) -> Self::Output {
-        let Point(x, y) = self;
-
-        Point(x + dx, y + dy)
-    }
-}
-
-impl Add<&Point> for &Point {
-    type Output = Point;
-
-    fn add(self, rhs: &Point) -> Self::Output {
-        Point::add(*self, *rhs)
-    }
+/// Where a step towards `direction` leads. Rows count down from the top of the scan.
+fn step(point: Point, direction: Direction) -> Point {
+    point + direction.unit(YAxis::Down)
 }
 
-const NW: Point = Point(-1, 1);
-const N: Point = Point(0, 1);
-const NE: Point = Point(1, 1);
-const E: Point = Point(1, 0);
-const SE: Point = Point(1, -1);
-const S: Point = Point(0, -1);
-const SW: Point = Point(-1, -1);
-const W: Point = Point(-1, 0);
+/// The direction an elf considers moving in, then the two diagonals next to it.
+type DirCheck = [Direction; 3];
 
-type DirCheck = [Point; 3];
-
-const N_DIR: DirCheck = [N, NE, NW];
-const S_DIR: DirCheck = [S, SE, SW];
-const W_DIR: DirCheck = [W, NW, SW];
-const E_DIR: DirCheck = [E, NE, SE];
+const N_DIR: DirCheck = [Direction::N, Direction::NE, Direction::NW];
+const S_DIR: DirCheck = [Direction::S, Direction::SE, Direction::SW];
+const W_DIR: DirCheck = [Direction::W, Direction::NW, Direction::SW];
+const E_DIR: DirCheck = [Direction::E, Direction::NE, Direction::SE];
 
 const INITIAL_DIR_ORDER: [DirCheck; 4] = [N_DIR, S_DIR, W_DIR, E_DIR];
-const SURROUNDING_TILES: [Point; 8] = [NW, N, NE, W, E, SW, S, SE];
 
 #[derive(Clone, Copy, Debug)]
 struct Elf {
@@ -60,73 +50,99 @@ impl Elf {
 }
 
 #[derive(Clone)]
-struct Elves {
+pub struct Elves {
     elves: Vec<Elf>,
+    store: StoreKind,
 }
 
-impl Elves {
-    fn positions(&self) -> HashSet<Point> {
-        self.elves.iter().map(|e| e.position).collect()
-    }
+/// Where the elves stand and how many propose each destination, kept between rounds so a
+/// `DenseGrid` only grows as the elves spread out.
+#[derive(Default)]
+struct Scratch<S, C> {
+    positions: S,
+    proposals: C,
 }
 
 fn propose_direction(
     index: usize,
     elf: &Elf,
-    positions: &HashSet<Point>,
+    positions: &impl GridStore<()>,
     directions: &VecDeque<DirCheck>,
 ) -> Option<(usize, Point)> {
     directions.iter().find_map(|direction| {
         let test = direction
             .iter()
-            .map(|p| *p + elf.position)
-            .find(|p| positions.contains(p));
+            .map(|&d| step(elf.position, d))
+            .find(|&p| positions.contains(p));
 
         match test {
             Some(_) => None,
-            None => Some((index, elf.position + direction[0])),
+            None => Some((index, step(elf.position, direction[0]))),
         }
     })
 }
 
-fn simulate_round(mut elves: Elves, dirs: &VecDeque<[Point; 3]>) -> (Elves, bool) {
-    let positions = elves.positions();
-
-    let needs_to_move = positions
-        .iter()
-        .flat_map(|p| SURROUNDING_TILES.iter().map(|dz| *dz + *p))
-        .collect::<HashSet<_>>();
+/// Moves the elves once, returning them and how many moved.
+fn simulate_round<S: GridStore<()>, C: GridStore<usize>>(
+    mut elves: Elves,
+    dirs: &VecDeque<DirCheck>,
+    scratch: &mut Scratch<S, C>,
+) -> (Elves, usize) {
+    let Scratch {
+        positions,
+        proposals,
+    } = scratch;
+    positions.clear();
+    for elf in &elves.elves {
+        positions.insert(elf.position, ());
+    }
 
     let mut to_move = elves
         .elves
         .iter()
         .enumerate()
-        .filter(|(_, elf)| needs_to_move.contains(&elf.position))
-        .filter_map(|(index, elf)| propose_direction(index, elf, &positions, dirs))
+        .filter(|(_, elf)| {
+            Direction::ALL
+                .iter()
+                .any(|&d| positions.contains(step(elf.position, d)))
+        })
+        .filter_map(|(index, elf)| propose_direction(index, elf, positions, dirs))
         .collect::<Vec<_>>();
 
-    let mut counts: HashMap<Point, usize> = HashMap::with_capacity(to_move.len());
+    proposals.clear();
     for (_, dest) in to_move.iter() {
-        *counts.entry(*dest).or_insert(0) += 1;
+        *proposals.get_or_insert_with(*dest, || 0) += 1;
     }
 
-    to_move.retain(|(_, p)| counts[p] == 1);
+    to_move.retain(|(_, p)| proposals.get(*p) == Some(&1));
 
     for (index, dest) in to_move.iter() {
         elves.elves[*index].set_position(*dest);
     }
-    (elves, !to_move.is_empty())
+    let moved = to_move.len();
+    (elves, moved)
 }
 
-fn part_one(mut elves: Elves, mut dirs: VecDeque<DirCheck>) -> usize {
+fn empty_ground_after_ten<S: GridStore<()>, C: GridStore<usize>>(
+    mut elves: Elves,
+    mut dirs: VecDeque<DirCheck>,
+    stats: &mut impl Stats,
+) -> usize {
+    let mut scratch = Scratch::<S, C>::default();
+    let mut moved;
     for _ in 1..=10 {
-        (elves, _) = simulate_round(elves, &dirs);
+        (elves, moved) = simulate_round(elves, &dirs, &mut scratch);
+        stats.count("rounds", 1);
+        stats.count("moves", moved as u64);
         dirs.rotate_left(1);
     }
 
-    let (x1, x2, y1, y2) = elves.positions().iter().fold(
+    let (x1, x2, y1, y2) = elves.elves.iter().fold(
         (isize::MAX, isize::MIN, isize::MAX, isize::MIN),
-        |(x1, x2, y1, y2), &Point(x, y)| (x1.min(x), x2.max(x), y1.min(y), y2.max(y)),
+        |(x1, x2, y1, y2),
+         &Elf {
+             position: Point { x, y },
+         }| (x1.min(x), x2.max(x), y1.min(y), y2.max(y)),
     );
 
     let b = x1.abs_diff(x2) + 1;
@@ -134,11 +150,18 @@ fn part_one(mut elves: Elves, mut dirs: VecDeque<DirCheck>) -> usize {
     (b * h) - elves.elves.len()
 }
 
-fn part_two(mut elves: Elves, mut dirs: VecDeque<DirCheck>) -> usize {
-    let mut did_move;
+fn first_still_round<S: GridStore<()>, C: GridStore<usize>>(
+    mut elves: Elves,
+    mut dirs: VecDeque<DirCheck>,
+    stats: &mut impl Stats,
+) -> usize {
+    let mut scratch = Scratch::<S, C>::default();
+    let mut moved;
     for n in 1.. {
-        (elves, did_move) = simulate_round(elves, &dirs);
-        if !did_move {
+        (elves, moved) = simulate_round(elves, &dirs, &mut scratch);
+        stats.count("rounds", 1);
+        stats.count("moves", moved as u64);
+        if moved == 0 {
             return n;
         }
         dirs.rotate_left(1);
@@ -146,37 +169,93 @@ fn part_two(mut elves: Elves, mut dirs: VecDeque<DirCheck>) -> usize {
     unreachable!()
 }
 
-fn parse_input(input: &str) -> Elves {
-    let elves = input
-        .lines()
-        .rev()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.bytes()
-                .enumerate()
-                .filter(|&(_, b)| b == b'#')
-                .map(move |(x, _)| Elf::new(Point(x as isize, y as isize)))
-        })
-        .collect::<Vec<_>>();
+fn parse_input(input: &str, store: StoreKind) -> Result<Elves, SolveError> {
+    let grove = Grid::parse_padded(input, false, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let elves = grove.find_all(&true).map(Elf::new).collect::<Vec<_>>();
 
-    Elves { elves }
+    Ok(Elves { elves, store })
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let dirs = VecDeque::from_iter(INITIAL_DIR_ORDER);
-    let elves = parse_input(input);
+pub struct Day23;
+
+impl Day for Day23 {
+    type Input<'a> = Elves;
 
-    let p1 = part_one(elves.clone(), dirs.clone());
-    let p2 = part_two(elves, dirs);
+    const PARAMS: &'static [Param] = &[Param::new(
+        "store",
+        "dense",
+        "map of the elves: dense or sparse",
+    )];
 
-    (Solution::USize(p1), Solution::USize(p2))
+    fn parse(input: &str) -> Result<Elves, SolveError> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Elves, SolveError> {
+        parse_input(input, params.get("store")?)
+    }
+
+    fn part_one(elves: &Elves) -> Result<Solution, SolveError> {
+        Self::part_one_stats(elves, &mut NoStats)
+    }
+
+    fn part_two(elves: &Elves) -> Result<Solution, SolveError> {
+        Self::part_two_stats(elves, &mut NoStats)
+    }
+
+    fn part_one_stats(elves: &Elves, stats: &mut impl Stats) -> Result<Solution, SolveError> {
+        let dirs = VecDeque::from_iter(INITIAL_DIR_ORDER);
+        let p1 = match elves.store {
+            StoreKind::Dense => {
+                empty_ground_after_ten::<DenseGrid<_>, DenseGrid<_>>(elves.clone(), dirs, stats)
+            }
+            StoreKind::Sparse => {
+                empty_ground_after_ten::<SparseGrid<_>, SparseGrid<_>>(elves.clone(), dirs, stats)
+            }
+        };
+
+        Ok(Solution::USize(p1))
+    }
+
+    fn part_two_stats(elves: &Elves, stats: &mut impl Stats) -> Result<Solution, SolveError> {
+        let dirs = VecDeque::from_iter(INITIAL_DIR_ORDER);
+        let p2 = match elves.store {
+            StoreKind::Dense => {
+                first_still_round::<DenseGrid<_>, DenseGrid<_>>(elves.clone(), dirs, stats)
+            }
+            StoreKind::Sparse => {
+                first_still_round::<SparseGrid<_>, SparseGrid<_>>(elves.clone(), dirs, stats)
+            }
+        };
+
+        Ok(Solution::USize(p2))
+    }
+
+    /// `size` elves scattered over a square grove about twice their number in area.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let side = (1..).find(|side| side * side >= 2 * size).unwrap().max(1);
+        let mut grove = Grid::new(side, side, '.');
+        for i in rng.sample(side * side, size) {
+            grove[Vector2::new_usize(i % side, i / side)] = '#';
+        }
+        Some(grove.to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::{solver::Solver, Solution};
+
     #[test]
     fn solve() {
         let input = include_str!("../../input/day23/test.txt");
-        super::solve(input);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::USize(110));
+        assert_eq!(p2, Solution::USize(20));
     }
 }
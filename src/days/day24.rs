@@ -1,25 +1,34 @@
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
+    collections::{BinaryHeap, HashSet},
 };
 
 use crate::{
-    utils::vector_2d::{Vector2, E, N, S, W},
-    Solution, SolutionPair,
+    etc::{
+        error::SolveError,
+        solver::{Day, Puzzle},
+        stats::{NoStats, Stats},
+    },
+    utils::{
+        grid::Grid,
+        vector_2d::{Direction, Vector2, YAxis, E, N, S, W},
+    },
+    Solution,
 };
 
-type Bounds = (usize, usize);
+pub const PUZZLE: Puzzle<Day24> = Puzzle::new(super::YEAR, 24, "Blizzard Basin");
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 enum Entity {
+    Ground,
     Blizzard(Vector2),
     Entrance,
     Exit,
     Wall,
 }
-struct Canyon {
-    bounds: Bounds,
-    entities: HashMap<Vector2, Entity>,
+
+pub struct Canyon {
+    entities: Grid<Entity>,
 }
 
 #[derive(Debug, Eq)]
@@ -52,8 +61,10 @@ impl PartialEq for HeapData {
 
 impl Canyon {
     fn position_at_offset(&self, position: Vector2, offsets: Vector2) -> Vector2 {
-        let (width, height) = self.bounds;
-        let (width, height) = (width as isize, height as isize);
+        let (width, height) = (
+            self.entities.width() as isize,
+            self.entities.height() as isize,
+        );
 
         Vector2 {
             x: (position.x - 1 + offsets.x).rem_euclid(width - 2) + 1,
@@ -69,23 +80,12 @@ impl Canyon {
         ]
     }
 
-    fn is_in_bounds(&self, v: Vector2) -> bool {
-        let (width, height) = self.bounds;
-        let (x, y) = (v.x, v.y);
-
-        x.is_positive() && y.is_positive() && (x as usize).lt(&width) && (y as usize).lt(&height)
-    }
-
     fn can_move_to(&self, position: Vector2, time: usize) -> bool {
-        match self.entities.get(&position) {
+        match self.entities.get(position) {
             Some(Entity::Exit) => true,
             Some(Entity::Entrance) => true,
-            Some(Entity::Wall) => false,
-            _ => {
-                if !self.is_in_bounds(position) {
-                    return false;
-                }
-
+            Some(Entity::Wall) | None => false,
+            Some(Entity::Ground | Entity::Blizzard(_)) => {
                 let blizzards = self
                     .positions_at_offset(position, time)
                     .iter()
@@ -97,7 +97,7 @@ impl Canyon {
                         3 => (N, p),
                         _ => unreachable!(),
                     })
-                    .filter(|(v, p)| match self.entities.get(p) {
+                    .filter(|(v, p)| match self.entities.get(**p) {
                         Some(Entity::Blizzard(d)) => *v == *d,
                         _ => false,
                     })
@@ -109,23 +109,18 @@ impl Canyon {
     }
 
     fn find_entrance_exit(&self) -> (Vector2, Vector2) {
-        let mut entrance_key = None;
-        let mut exit_key = None;
-        let mut iter = self.entities.iter();
-        while entrance_key.is_none() || exit_key.is_none() {
-            match iter.next() {
-                Some((k, v)) => match v {
-                    Entity::Entrance => entrance_key = Some(*k),
-                    Entity::Exit => exit_key = Some(*k),
-                    _ => (),
-                },
-                None => break,
-            }
-        }
-        (entrance_key.unwrap(), exit_key.unwrap())
+        let entrance = self.entities.find(&Entity::Entrance);
+        let exit = self.entities.find(&Entity::Exit);
+        (entrance.unwrap(), exit.unwrap())
     }
 
-    fn find_path(&self, start: Vector2, end: Vector2, start_time: usize) -> usize {
+    fn find_path(
+        &self,
+        start: Vector2,
+        end: Vector2,
+        start_time: usize,
+        stats: &mut impl Stats,
+    ) -> Option<usize> {
         let mut queue = BinaryHeap::new();
 
         queue.push(HeapData {
@@ -137,8 +132,9 @@ impl Canyon {
         let mut visited = HashSet::new();
 
         while let Some(data) = queue.pop() {
+            stats.count("heap pops", 1);
             if data.point == end {
-                return data.time;
+                return Some(data.time);
             } else {
                 let time = data.time + 1;
                 if self.can_move_to(data.point, time) {
@@ -162,66 +158,84 @@ impl Canyon {
                 }
             }
         }
-        0
+        None
+    }
+}
+
+fn parse_input(input: &str) -> Result<Canyon, SolveError> {
+    let mut entities = Grid::parse(input, |c| match c {
+        '#' => Some(Entity::Wall),
+        '.' => Some(Entity::Ground),
+        '^' | '>' | 'v' | '<' => Direction::try_from(c)
+            .ok()
+            .map(|d| Entity::Blizzard(d.unit(YAxis::Down))),
+        _ => None,
+    })?;
+    let (width, height) = (entities.width(), entities.height());
+    if width < 3 || height < 3 {
+        return Err(SolveError::new(
+            "expected a walled valley at least 3 tiles wide and high",
+        ));
     }
+
+    entities[Vector2::new_usize(1, 0)] = Entity::Entrance;
+    entities[Vector2::new_usize(width - 2, height - 1)] = Entity::Exit;
+
+    Ok(Canyon { entities })
 }
 
-fn parse_input(input: &str) -> Canyon {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    let bounds = (width, height);
-
-    let mut entities = input
-        .lines()
-        .rev()
-        .enumerate()
-        .flat_map(|(y, l)| {
-            l.bytes().enumerate().map(move |(x, b)| {
-                let x = x as isize;
-                let y = y as isize;
-                (Vector2 { x, y }, b)
-            })
-        })
-        .filter_map(|(p, b)| match b {
-            b'#' => Some((p, Entity::Wall)),
-            b'^' => Some((p, Entity::Blizzard(N))),
-            b'>' => Some((p, Entity::Blizzard(E))),
-            b'<' => Some((p, Entity::Blizzard(W))),
-            b'v' => Some((p, Entity::Blizzard(S))),
-            _ => None,
-        })
-        .collect::<HashMap<_, _>>();
-
-    let x = 1;
-    let y = height as isize - 1;
-    entities.insert(Vector2 { x, y }, Entity::Entrance);
-
-    let x = width as isize - 2;
-    let y = 0;
-    entities.insert(Vector2 { x, y }, Entity::Exit);
-
-    Canyon { entities, bounds }
+fn no_path() -> SolveError {
+    SolveError::new("the blizzards block every path")
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let map = parse_input(input);
+pub struct Day24;
 
-    let (start, end) = map.find_entrance_exit();
-    let start_to_end = map.find_path(start, end, 0);
-    let end_to_start = map.find_path(end, start, start_to_end);
-    let and_back_again = map.find_path(start, end, end_to_start);
+impl Day for Day24 {
+    type Input<'a> = Canyon;
 
-    (
-        Solution::USize(start_to_end),
-        Solution::USize(and_back_again),
-    )
+    fn parse(input: &str) -> Result<Canyon, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_one(map: &Canyon) -> Result<Solution, SolveError> {
+        Self::part_one_stats(map, &mut NoStats)
+    }
+
+    fn part_two(map: &Canyon) -> Result<Solution, SolveError> {
+        Self::part_two_stats(map, &mut NoStats)
+    }
+
+    fn part_one_stats(map: &Canyon, stats: &mut impl Stats) -> Result<Solution, SolveError> {
+        let (start, end) = map.find_entrance_exit();
+        let start_to_end = map.find_path(start, end, 0, stats).ok_or_else(no_path)?;
+
+        Ok(Solution::USize(start_to_end))
+    }
+
+    fn part_two_stats(map: &Canyon, stats: &mut impl Stats) -> Result<Solution, SolveError> {
+        let (start, end) = map.find_entrance_exit();
+        let start_to_end = map.find_path(start, end, 0, stats).ok_or_else(no_path)?;
+        let end_to_start = map
+            .find_path(end, start, start_to_end, stats)
+            .ok_or_else(no_path)?;
+        let and_back_again = map
+            .find_path(start, end, end_to_start, stats)
+            .ok_or_else(no_path)?;
+
+        Ok(Solution::USize(and_back_again))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::{solver::Solver, Solution};
+
     #[test]
     fn solve() {
         let input = include_str!("../../input/day24/test.txt");
-        _ = super::solve(input);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::USize(18));
+        assert_eq!(p2, Solution::USize(54));
     }
 }
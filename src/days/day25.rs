@@ -1,9 +1,19 @@
 use std::{iter::Sum, ops::Add};
 
-use crate::{Solution, SolutionPair};
-
-#[derive(Default)]
-struct Fuel {
+use crate::{
+    etc::{
+        error::SolveError,
+        input,
+        solver::{Day, Puzzle},
+    },
+    utils::rng::Rng,
+    Solution,
+};
+
+pub const PUZZLE: Puzzle<Day25> = Puzzle::new(super::YEAR, 25, "Full of Hot Air");
+
+#[derive(Clone, Default)]
+pub struct Fuel {
     value: i64,
 }
 
@@ -34,81 +44,114 @@ impl Sum for Fuel {
 }
 
 impl Fuel {
-    fn from_snafu(snafu: &str) -> Fuel {
-        let value = snafu
-            .bytes()
-            .rev()
-            .enumerate()
-            .map(|(i, b)| {
-                let bit = 5_i64.pow(i as u32);
-                let v: i64 = match b {
-                    b'=' => -2,
-                    b'-' => -1,
-                    b'0' => 0,
-                    b'1' => 1,
-                    b'2' => 2,
-                    _ => unreachable!(),
-                };
-                v * bit
-            })
-            .sum();
+    fn from_snafu(snafu: &str) -> Option<Fuel> {
+        if snafu.is_empty() {
+            return None;
+        }
 
-        Fuel { value }
+        let value = snafu.bytes().try_fold(0_i128, |value, b| {
+            let digit = match b {
+                b'=' => -2,
+                b'-' => -1,
+                b'0' => 0,
+                b'1' => 1,
+                b'2' => 2,
+                _ => return None,
+            };
+            value.checked_mul(5)?.checked_add(digit)
+        })?;
+
+        Some(Fuel {
+            value: value.try_into().ok()?,
+        })
     }
 
     fn to_snafu(&self) -> String {
-        let mut n = self.value;
-        let l = (n as f64).log(5.0).ceil();
-        let mut digits = Vec::with_capacity(l as usize);
-
-        let to_snafu_digit = |r| match r {
-            3 => '=',
-            4 => '-',
-            0 => '0',
-            1 => '1',
-            2 => '2',
-            _ => unreachable!(),
-        };
-
-        while n != 0 {
-            let r = n % 5;
-            let digit = to_snafu_digit(r);
+        // Widened so that `n + 2` cannot overflow at the ends of the i64 range.
+        let mut n = self.value as i128;
+        let mut digits = Vec::new();
+
+        loop {
+            let digit = match n.rem_euclid(5) {
+                3 => '=',
+                4 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!(),
+            };
             digits.push(digit);
-            n = (n + 2) / 5;
+            n = (n + 2).div_euclid(5);
+            if n == 0 {
+                break;
+            }
         }
 
         digits.iter().rev().collect()
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let p1 = input.lines().map(Fuel::from_snafu).sum::<Fuel>().to_snafu();
-    let p2: u64 = 0;
+pub struct Day25;
+
+impl Day for Day25 {
+    type Input<'a> = Vec<Fuel>;
+
+    fn parse(input: &str) -> Result<Vec<Fuel>, SolveError> {
+        input::lines(input)
+            .map(|line| {
+                Fuel::from_snafu(line)
+                    .ok_or_else(|| SolveError::at(input, line, "expected a SNAFU number"))
+            })
+            .collect()
+    }
+
+    fn part_one(fuel: &Vec<Fuel>) -> Result<Solution, SolveError> {
+        let p1 = fuel.iter().cloned().sum::<Fuel>().to_snafu();
+
+        Ok(Solution::Str(p1))
+    }
+
+    fn part_two(_: &Vec<Fuel>) -> Result<Solution, SolveError> {
+        Ok(Solution::Unsolved)
+    }
 
-    (Solution::Str(p1), Solution::U64(p2))
+    /// `size` SNAFU numbers of up to 16 digits.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let numbers = (0..size).map(|_| {
+            Fuel {
+                value: rng.range(1..=5_i64.pow(15)),
+            }
+            .to_snafu()
+        });
+        Some(numbers.collect::<Vec<_>>().join("\n"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day25::Fuel;
+    use crate::{
+        days::day25::Fuel,
+        etc::{solver::Solver, Solution},
+        utils::prop,
+    };
 
     #[test]
     fn from_snafu_test() {
-        assert_eq!(1, Fuel::from_snafu("1").value);
-        assert_eq!(2, Fuel::from_snafu("2").value);
-        assert_eq!(3, Fuel::from_snafu("1=").value);
-        assert_eq!(4, Fuel::from_snafu("1-").value);
-        assert_eq!(5, Fuel::from_snafu("10").value);
-        assert_eq!(6, Fuel::from_snafu("11").value);
-        assert_eq!(7, Fuel::from_snafu("12").value);
-        assert_eq!(8, Fuel::from_snafu("2=").value);
-        assert_eq!(9, Fuel::from_snafu("2-").value);
-        assert_eq!(10, Fuel::from_snafu("20").value);
-        assert_eq!(15, Fuel::from_snafu("1=0").value);
-        assert_eq!(20, Fuel::from_snafu("1-0").value);
-        assert_eq!(2022, Fuel::from_snafu("1=11-2").value);
-        assert_eq!(12345, Fuel::from_snafu("1-0---0").value);
-        assert_eq!(314159265, Fuel::from_snafu("1121-1110-1=0").value);
+        assert_eq!(1, Fuel::from_snafu("1").unwrap().value);
+        assert_eq!(2, Fuel::from_snafu("2").unwrap().value);
+        assert_eq!(3, Fuel::from_snafu("1=").unwrap().value);
+        assert_eq!(4, Fuel::from_snafu("1-").unwrap().value);
+        assert_eq!(5, Fuel::from_snafu("10").unwrap().value);
+        assert_eq!(6, Fuel::from_snafu("11").unwrap().value);
+        assert_eq!(7, Fuel::from_snafu("12").unwrap().value);
+        assert_eq!(8, Fuel::from_snafu("2=").unwrap().value);
+        assert_eq!(9, Fuel::from_snafu("2-").unwrap().value);
+        assert_eq!(10, Fuel::from_snafu("20").unwrap().value);
+        assert_eq!(15, Fuel::from_snafu("1=0").unwrap().value);
+        assert_eq!(20, Fuel::from_snafu("1-0").unwrap().value);
+        assert_eq!(2022, Fuel::from_snafu("1=11-2").unwrap().value);
+        assert_eq!(12345, Fuel::from_snafu("1-0---0").unwrap().value);
+        assert_eq!(314159265, Fuel::from_snafu("1121-1110-1=0").unwrap().value);
     }
 
     #[test]
@@ -130,11 +173,47 @@ mod tests {
         assert_eq!(Fuel { value: 314159265 }.to_snafu(), "1121-1110-1=0");
     }
 
+    #[test]
+    fn zero_and_negatives() {
+        assert_eq!(Fuel { value: 0 }.to_snafu(), "0");
+        assert_eq!(Fuel { value: -1 }.to_snafu(), "-");
+        assert_eq!(Fuel { value: -3 }.to_snafu(), "-2");
+        assert_eq!(Fuel::from_snafu("0").unwrap().value, 0);
+        assert!(Fuel::from_snafu("").is_none());
+        assert!(Fuel::from_snafu("3").is_none());
+    }
+
+    #[test]
+    fn snafu_round_trips() {
+        prop::check(
+            |rng| {
+                // Mostly small numbers, with zero and the ends of the range mixed in.
+                match rng.below(8) {
+                    0 => *rng.pick(&[0, 1, -1, i64::MIN, i64::MAX]),
+                    1 => rng.range(i64::MIN..=i64::MAX),
+                    _ => rng.range(-100_000..=100_000),
+                }
+            },
+            |&value| {
+                let snafu = Fuel { value }.to_snafu();
+                Fuel::from_snafu(&snafu).map(|f| f.value) == Some(value)
+            },
+        );
+    }
+
+    #[test]
+    fn out_of_range_snafu_is_rejected() {
+        let max = Fuel { value: i64::MAX }.to_snafu();
+        assert!(Fuel::from_snafu(&format!("{}0", max)).is_none());
+        assert!(Fuel::from_snafu(&"2".repeat(100)).is_none());
+    }
+
     #[test]
     fn solve() {
         let input = include_str!("../../input/day25/test.txt");
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
 
-        println!("{p1}, {p2}");
+        assert_eq!(p1, Solution::from("2=-1=0"));
+        assert_eq!(p2, Solution::Unsolved);
     }
 }
@@ -23,3 +23,65 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+use crate::etc::solver::Solver;
+
+pub const YEAR: u16 = 2022;
+
+pub static PUZZLES: &[&dyn Solver] = &[
+    &day01::PUZZLE,
+    &day02::PUZZLE,
+    &day03::PUZZLE,
+    &day04::PUZZLE,
+    &day05::PUZZLE,
+    &day06::PUZZLE,
+    &day07::PUZZLE,
+    &day08::PUZZLE,
+    &day09::PUZZLE,
+    &day10::PUZZLE,
+    &day11::PUZZLE,
+    &day12::PUZZLE,
+    &day13::PUZZLE,
+    &day14::PUZZLE,
+    &day15::PUZZLE,
+    &day16::PUZZLE,
+    &day17::PUZZLE,
+    &day18::PUZZLE,
+    &day19::PUZZLE,
+    &day20::PUZZLE,
+    &day21::PUZZLE,
+    &day22::PUZZLE,
+    &day23::PUZZLE,
+    &day24::PUZZLE,
+    &day25::PUZZLE,
+];
+
+#[cfg(test)]
+mod tests {
+    /// About the size of a real input, like the 99 by 99 trees of day 8, and no larger:
+    /// day 12's part two searches from every `a`, which takes too long for a debug build on
+    /// much larger maps. `gen` itself takes any size.
+    const REALISTIC_SIZE: usize = 100;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for solver in super::PUZZLES {
+            for (size, seed) in [(30, 0), (30, 1), (30, 2), (REALISTIC_SIZE, 0)] {
+                if let Some(text) = solver.generate(size, seed) {
+                    if let Err(e) = solver.solve(&text) {
+                        panic!(
+                            "generated input of size {} for seed {} is invalid: {}\n{}",
+                            size, seed, e, text
+                        );
+                    }
+                    assert_eq!(
+                        solver.generate(size, seed),
+                        Some(text),
+                        "day {} generator is not seeded",
+                        solver.day()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use super::{
+    input::{self, InputError},
+    params::Params,
+    Solution,
+};
+
+pub const ANSWERS_FILE: &str = "answers.json";
+
+/// Expected answers for an event, read from `answers.json` in its input directory:
+///
+/// ```json
+/// { "1": { "real": [70374, 204610], "test": [24000, 45000] } }
+/// ```
+///
+/// Each input name maps to `[part 1, part 2]`, `null` marks a part without a known answer.
+/// An input that needs other parameters than the day's defaults lists them next to its
+/// answers:
+///
+/// ```json
+/// { "15": { "test": { "answers": [26, 56000011], "params": { "y": 10, "size": 20 } } } }
+/// ```
+#[derive(Default)]
+pub struct Answers {
+    expected: HashMap<(u8, String), [Option<String>; 2]>,
+    params: HashMap<(u8, String), Params>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Read(InputError),
+    Parse(PathBuf, String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            AnswersError::Read(e) => e.fmt(f),
+            AnswersError::Parse(path, message) => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Missing,
+    /// The part is not implemented and has no recorded answer either.
+    Unsolved,
+}
+
+impl Answers {
+    pub fn path(input_dir: &str) -> PathBuf {
+        Path::new(input_dir).join(ANSWERS_FILE)
+    }
+
+    pub fn load(input_dir: &str) -> Result<Answers, AnswersError> {
+        let path = Answers::path(input_dir);
+        let text = input::read_input(&path).map_err(AnswersError::Read)?;
+        Answers::parse(&text).map_err(|message| AnswersError::Parse(path, message))
+    }
+
+    fn parse(text: &str) -> Result<Answers, String> {
+        let json: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let days = json.as_object().ok_or("expected an object keyed by day")?;
+
+        let mut expected = HashMap::new();
+        let mut params = HashMap::new();
+        for (day, inputs) in days {
+            let day = day
+                .parse::<u8>()
+                .map_err(|_| format!("{:?} is not a day", day))?;
+            let inputs = inputs
+                .as_object()
+                .ok_or_else(|| format!("day {}: expected an object keyed by input name", day))?;
+
+            for (name, entry) in inputs {
+                let parts = match entry.get("params") {
+                    Some(values) => {
+                        let values = values.as_object().ok_or_else(|| {
+                            format!("day {} {}: expected an object of parameters", day, name)
+                        })?;
+                        let mut input_params = Params::default();
+                        for (param, value) in values {
+                            // Numbers are written as numbers, the day parses them from text.
+                            input_params.set(param, &answer_text(value).unwrap_or_default());
+                        }
+                        params.insert((day, name.clone()), input_params);
+                        entry.get("answers").unwrap_or(&Value::Null)
+                    }
+                    None => entry,
+                };
+                let parts = match parts.as_array().map(Vec::as_slice) {
+                    Some([p1, p2]) => [answer_text(p1), answer_text(p2)],
+                    _ => return Err(format!("day {} {}: expected [part 1, part 2]", day, name)),
+                };
+                expected.insert((day, name.clone()), parts);
+            }
+        }
+        Ok(Answers { expected, params })
+    }
+
+    /// The parameters recorded for `input` of `day`, empty if it uses the defaults.
+    pub fn params(&self, day: u8, input: &str) -> Params {
+        self.params
+            .get(&(day, input.to_owned()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Input names that have answers recorded for `day`, sorted.
+    pub fn inputs(&self, day: u8) -> Vec<&str> {
+        let mut names = self
+            .expected
+            .keys()
+            .filter(|(d, _)| *d == day)
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn expected(&self, day: u8, part: usize, input: &str) -> Option<&str> {
+        self.expected
+            .get(&(day, input.to_owned()))
+            .and_then(|parts| parts[part - 1].as_deref())
+    }
+
+    pub fn check(&self, day: u8, part: usize, input: &str, actual: &Solution) -> Verdict {
+        match self.expected(day, part, input) {
+            None if !actual.is_solved() => Verdict::Unsolved,
+            None => Verdict::Missing,
+            Some(expected) if expected == actual.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_owned()),
+        }
+    }
+}
+
+fn answer_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_check() {
+        let answers =
+            Answers::parse(r#"{ "3": { "test": [157, null], "real": ["CMZ", 12] } }"#).unwrap();
+
+        assert!(matches!(
+            answers.check(3, 1, "test", &Solution::USize(157)),
+            Verdict::Pass
+        ));
+        assert!(matches!(
+            answers.check(3, 2, "test", &Solution::USize(70)),
+            Verdict::Missing
+        ));
+        assert!(matches!(
+            answers.check(3, 2, "test", &Solution::Unsolved),
+            Verdict::Unsolved
+        ));
+        assert!(matches!(
+            answers.check(3, 1, "test", &Solution::Unsolved),
+            Verdict::Fail(_)
+        ));
+        assert!(matches!(
+            answers.check(3, 1, "real", &Solution::Str("MCD".into())),
+            Verdict::Fail(_)
+        ));
+        assert_eq!(answers.inputs(3), ["real", "test"]);
+    }
+
+    #[test]
+    fn rejects_malformed_parts() {
+        assert!(Answers::parse(r#"{ "3": { "test": [157] } }"#).is_err());
+        assert!(Answers::parse(r#"{ "3": { "test": { "params": { "y": 10 } } } }"#).is_err());
+        assert!(
+            Answers::parse(r#"{ "3": { "test": { "answers": [1, 2], "params": 10 } } }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn inputs_carry_params() {
+        let answers =
+            Answers::parse(r#"{ "15": { "test": { "answers": [26, null], "params": { "y": 10, "start": "AA" } } } }"#)
+                .unwrap();
+
+        assert!(matches!(
+            answers.check(15, 1, "test", &Solution::I64(26)),
+            Verdict::Pass
+        ));
+        let params = answers.params(15, "test");
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            [("start", "AA"), ("y", "10")]
+        );
+        assert!(answers.params(15, "real").is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    any::type_name,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// Where in the puzzle input a problem was found. Lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveError {
+    /// Filled in by the `Puzzle` wrapper, solvers leave it at 0.
+    pub day: u8,
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> SolveError {
+        SolveError {
+            day: 0,
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Reports `text` as the offending fragment. `text` has to be a slice of
+    /// `input` for the line and column to be found, otherwise the error is
+    /// reported without a location.
+    pub fn at(input: &str, text: &str, message: impl Into<String>) -> SolveError {
+        let start = input.as_ptr() as usize;
+        let offset = (text.as_ptr() as usize).wrapping_sub(start);
+
+        let inside = offset <= input.len() && text.len() <= input.len() - offset;
+
+        let location = inside.then(|| {
+            let before = &input[..offset];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let column = before[line_start..].chars().count() + 1;
+
+            Location {
+                line,
+                column,
+                text: text.to_owned(),
+            }
+        });
+
+        SolveError {
+            day: 0,
+            message: message.into(),
+            location,
+        }
+    }
+
+    pub fn with_day(self, day: u8) -> SolveError {
+        SolveError { day, ..self }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "day {:02}", self.day)?;
+        if let Some(location) = &self.location {
+            write!(f, ", line {}, column {}", location.line, location.column)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(location) = &self.location {
+            // Only quote the start of the fragment, it may span a whole block of input
+            let line = location.text.lines().next().unwrap_or_default();
+            match line.char_indices().nth(40) {
+                Some((i, _)) => write!(f, " (found {:?}...)", &line[..i])?,
+                None => write!(f, " (found {:?})", line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Parses `text`, a slice of `input`, reporting where it was found on failure.
+pub fn parse<T: FromStr>(input: &str, text: &str) -> Result<T, SolveError> {
+    text.parse()
+        .map_err(|_| SolveError::at(input, text, format!("expected {}", type_name::<T>())))
+}
+
+/// `str::split_once` that reports the line missing `delimiter` on failure.
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), SolveError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| SolveError::at(input, text, format!("expected {:?}", delimiter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_fragment() {
+        let input = "1-2\n3-x\n";
+        let text = &input[6..7];
+        let error = parse::<u32>(input, text).unwrap_err();
+        let location = error.location.unwrap();
+
+        assert_eq!((location.line, location.column), (2, 3));
+        assert_eq!(location.text, "x");
+    }
+
+    #[test]
+    fn foreign_text_has_no_location() {
+        let error = SolveError::at("abc", "abc".to_owned().as_str(), "oops");
+        assert_eq!(error.location, None);
+    }
+}
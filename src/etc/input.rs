@@ -0,0 +1,167 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub const REAL_INPUT: &str = "real";
+
+/// The input path that stands for standard input.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "could not read {}: {}", self.path.display(), self.source)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn input_path(input_dir: &str, day: u8, name: &str) -> PathBuf {
+    Path::new(input_dir)
+        .join(format!("day{:02}", day))
+        .join(format!("{}.txt", name))
+}
+
+/// Names of every `*.txt` input in `day`'s directory, like `real` and `test2`, sorted.
+/// A missing directory has no inputs.
+pub fn inputs(input_dir: &str, day: u8) -> Vec<String> {
+    let dir = Path::new(input_dir).join(format!("day{:02}", day));
+    let mut names = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            match path.extension() {
+                Some(ext) if ext == "txt" && path.is_file() => {
+                    Some(path.file_stem()?.to_string_lossy().into_owned())
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+/// Reads the file at `path`, or standard input if `path` is `-`.
+pub fn read_input(path: &Path) -> Result<String, InputError> {
+    let text = if path == Path::new(STDIN) {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).map(|_| text)
+    } else {
+        fs::read_to_string(path)
+    };
+
+    text.map(normalize).map_err(|source| InputError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Drops the byte order mark some editors and clipboards put in front of the text.
+/// Line endings are left alone, `lines` and `blocks` handle both.
+pub fn normalize(text: String) -> String {
+    match text.strip_prefix('\u{feff}') {
+        Some(rest) => rest.to_owned(),
+        None => text,
+    }
+}
+
+/// The lines of `input` with trailing whitespace (including a `\r` left by CRLF line endings)
+/// removed and any blank lines at the end of the input dropped. Leading whitespace is kept,
+/// some puzzles lay their input out in columns.
+pub fn lines(input: &str) -> impl DoubleEndedIterator<Item = &str> {
+    input.trim_end().lines().map(str::trim_end)
+}
+
+/// Splits `input` into blocks separated by one or more blank lines, whatever the line endings.
+/// Each block is a slice of `input` without its trailing line break, so it can still be used
+/// to locate errors.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    let mut lines = input.split_inclusive('\n').map(move |line| {
+        let start = offset;
+        offset += line.len();
+        (start, line.trim_end())
+    });
+
+    std::iter::from_fn(move || {
+        let (start, first) = lines.by_ref().find(|(_, line)| !line.is_empty())?;
+        let mut end = start + first.len();
+        for (start, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            end = start + line.len();
+        }
+        Some(&input[start..end])
+    })
+}
+
+/// `input` as exactly two blocks, like a map followed by a list of moves.
+pub fn two_blocks(input: &str) -> Option<(&str, &str)> {
+    let mut blocks = blocks(input);
+    let first = blocks.next()?;
+    let second = blocks.next()?;
+    blocks.next().is_none().then_some((first, second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `text` with LF and with CRLF line endings, whichever it was written with.
+    fn line_endings(text: &str) -> [String; 2] {
+        let lf = text.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+        [lf, crlf]
+    }
+
+    #[test]
+    fn blocks_ignore_line_endings() {
+        for text in line_endings("a\n b\n\n\nc  \n\nd\n\n") {
+            let blocks = blocks(&text).collect::<Vec<_>>();
+            assert_eq!(blocks.len(), 3);
+            assert_eq!(lines(blocks[0]).collect::<Vec<_>>(), ["a", " b"]);
+            assert_eq!(blocks[1], "c");
+            assert_eq!(blocks[2], "d");
+        }
+    }
+
+    #[test]
+    fn lines_are_trimmed() {
+        for text in line_endings("  1 \n2\n\n") {
+            assert_eq!(lines(&text).collect::<Vec<_>>(), ["  1", "2"]);
+        }
+    }
+
+    #[test]
+    fn finds_every_input() {
+        assert_eq!(inputs("input", 10)[..3], ["real", "test", "test2"]);
+        assert!(inputs("input", 10)
+            .iter()
+            .all(|name| !name.ends_with(".txt")));
+        assert!(inputs("no such dir", 1).is_empty());
+    }
+
+    #[test]
+    fn byte_order_mark_is_dropped() {
+        assert_eq!(normalize("\u{feff}1\r\n2".to_owned()), "1\r\n2");
+        assert_eq!(normalize("1\n".to_owned()), "1\n");
+    }
+
+    #[test]
+    fn two_blocks_only() {
+        assert_eq!(two_blocks("a\n\nb\n"), Some(("a", "b")));
+        assert_eq!(two_blocks("a\r\nb"), None);
+        assert_eq!(two_blocks("a\n\nb\n\nc"), None);
+    }
+}
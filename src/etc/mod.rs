@@ -1,3 +1,9 @@
+pub mod answers;
+pub mod error;
+pub mod input;
+pub mod params;
 pub mod solution;
+pub mod solver;
+pub mod stats;
 
-pub use solution::Solution;
\ No newline at end of file
+pub use solution::Solution;
@@ -0,0 +1,142 @@
+use std::{any::type_name, str::FromStr};
+
+use super::error::SolveError;
+
+/// A value a day needs besides its input text, like the row day 15 scans, that differs
+/// between the example and the real input. Days list theirs in `Day::PARAMS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value for the real input.
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, help: &'static str) -> Param {
+        Param {
+            name,
+            default,
+            help,
+        }
+    }
+}
+
+/// Parameter values by name, kept as text until a day asks for one. Later values replace
+/// earlier ones, so overrides are applied in order: `answers.json`, then `--param`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    /// The defaults of `declared`.
+    pub fn defaults(declared: &[Param]) -> Params {
+        let mut params = Params::default();
+        for param in declared {
+            params.set(param.name, param.default);
+        }
+        params
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        match self.values.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value.to_owned(),
+            None => self.values.push((name.to_owned(), value.to_owned())),
+        }
+    }
+
+    /// Applies every value of `overrides` on top of these.
+    pub fn extend(&mut self, overrides: &Params) {
+        for (name, value) in &overrides.values {
+            self.set(name, value);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    /// The defaults of `declared` with the values of these that `declared` has a parameter
+    /// for. Values for other parameters are ignored, they belong to another day.
+    pub fn resolve(&self, declared: &[Param]) -> Params {
+        let mut params = Params::defaults(declared);
+        for (name, value) in self.iter() {
+            if declared.iter().any(|p| p.name == name) {
+                params.set(name, value);
+            }
+        }
+        params
+    }
+
+    /// The text of `name`.
+    pub fn text(&self, name: &str) -> Result<&str, SolveError> {
+        self.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| SolveError::new(format!("no parameter {:?}", name)))
+    }
+
+    /// The value of `name` parsed as a `T`.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, SolveError> {
+        let text = self.text(name)?;
+        text.trim().parse().map_err(|_| {
+            let expected = type_name::<T>().rsplit("::").next().unwrap();
+            SolveError::new(format!(
+                "parameter {} expects {}, not {:?}",
+                name, expected, text
+            ))
+        })
+    }
+}
+
+/// Parses `name=value` as given to `--param`.
+impl FromStr for Params {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Params, String> {
+        let mut params = Params::default();
+        match text.split_once('=') {
+            Some((name, value)) if !name.is_empty() => params.set(name, value),
+            _ => return Err(format!("--param expects <name>=<value>, not {:?}", text)),
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param::new("y", "2000000", "row"),
+        Param::new("start", "AA", "valve"),
+    ];
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let mut overrides: Params = "y=10".parse().unwrap();
+        overrides.set("minutes", "26");
+        let params = overrides.resolve(DECLARED);
+
+        assert_eq!(params.get::<i64>("y"), Ok(10));
+        assert_eq!(params.text("start"), Ok("AA"));
+        assert!(params.text("minutes").is_err());
+    }
+
+    #[test]
+    fn bad_values_are_reported() {
+        let params = "y=ten".parse::<Params>().unwrap().resolve(DECLARED);
+
+        assert_eq!(
+            params.get::<i64>("y").unwrap_err().message,
+            "parameter y expects i64, not \"ten\""
+        );
+        assert!("y".parse::<Params>().is_err());
+        assert!("=3".parse::<Params>().is_err());
+    }
+}
@@ -1,6 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
 use Solution::*;
 
+/// The answer to one part of a puzzle. `Unsolved` marks a part that has no
+/// implementation yet, so it is never mistaken for an answer of zero.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Solution {
     I32(i32),
     I64(i64),
@@ -10,7 +14,30 @@ pub enum Solution {
     U128(u128),
     Str(String),
     USize(usize),
-    ISize(isize)
+    ISize(isize),
+    Unsolved,
+}
+
+impl Solution {
+    /// Name of the variant, e.g. `"USize"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            I32(_) => "I32",
+            I64(_) => "I64",
+            I128(_) => "I128",
+            U32(_) => "U32",
+            U64(_) => "U64",
+            U128(_) => "U128",
+            Str(_) => "Str",
+            USize(_) => "USize",
+            ISize(_) => "ISize",
+            Unsolved => "Unsolved",
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self != Unsolved
+    }
 }
 
 impl Display for Solution {
@@ -24,7 +51,67 @@ impl Display for Solution {
             U128(x) => x.fmt(f),
             Str(x) => x.fmt(f),
             USize(x) => x.fmt(f),
-            ISize(x) => x.fmt(f)
+            ISize(x) => x.fmt(f),
+            Unsolved => "not implemented".fmt(f),
         }
     }
-}
\ No newline at end of file
+}
+
+macro_rules! impl_from {
+    ($($t:ty => $variant:ident),*) => {
+        $(
+            impl From<$t> for Solution {
+                fn from(value: $t) -> Self {
+                    $variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    String => Str,
+    usize => USize,
+    isize => ISize
+);
+
+impl From<&str> for Solution {
+    fn from(value: &str) -> Self {
+        Str(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Solution::from(7usize), USize(7));
+        assert_eq!(Solution::from(-7i64), I64(-7));
+        assert_eq!(Solution::from("CMZ"), Str("CMZ".to_owned()));
+        assert_ne!(Solution::from(0u64), Unsolved);
+    }
+
+    #[test]
+    fn unsolved_is_not_zero() {
+        assert_eq!(Unsolved.to_string(), "not implemented");
+        assert!(!Unsolved.is_solved());
+        assert!(U64(0).is_solved());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for solution in [U128(u128::MAX), Str("\n#.".to_owned()), Unsolved] {
+            let json = serde_json::to_string(&solution).unwrap();
+            assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), solution);
+        }
+        assert_eq!(serde_json::to_string(&USize(3)).unwrap(), r#"{"USize":3}"#);
+    }
+}
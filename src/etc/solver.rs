@@ -0,0 +1,212 @@
+use std::{
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use crate::{utils::rng::Rng, Solution, SolutionPair};
+
+use super::{
+    error::SolveError,
+    params::{Param, Params},
+    stats::{NoStats, Recorder, Stats},
+};
+
+/// How long each phase of one solver run took.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+/// `duration` in milliseconds, the unit every timing is printed and recorded in.
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+pub trait Solver: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    /// Name of the example input in `input/dayNN/` used by `--test`.
+    fn test_input(&self) -> &'static str {
+        "test"
+    }
+
+    /// The parameters the day takes besides its input, see `Day::PARAMS`.
+    fn params(&self) -> &'static [Param];
+
+    /// Solves `input` with `params` overriding the defaults of the day's parameters, and
+    /// records stats if asked to. The recorders stay empty otherwise.
+    fn solve_with(
+        &self,
+        input: &str,
+        params: &Params,
+        stats: bool,
+    ) -> Result<(SolutionPair, Timings, [Recorder; 2]), SolveError>;
+
+    fn solve_timed(&self, input: &str) -> Result<(SolutionPair, Timings), SolveError> {
+        self.solve_with(input, &Params::default(), false)
+            .map(|(solutions, timings, _)| (solutions, timings))
+    }
+
+    /// Like `solve_timed`, also returning the stats each part recorded.
+    fn solve_with_stats(
+        &self,
+        input: &str,
+    ) -> Result<(SolutionPair, Timings, [Recorder; 2]), SolveError> {
+        self.solve_with(input, &Params::default(), true)
+    }
+
+    fn solve(&self, input: &str) -> Result<SolutionPair, SolveError> {
+        self.solve_timed(input).map(|(solutions, _)| solutions)
+    }
+
+    /// A random puzzle input of roughly `size` (see the day's `Day::generate`), the same
+    /// for the same seed. `None` if the day has no generator.
+    fn generate(&self, size: usize, seed: u64) -> Option<String>;
+}
+
+/// A day's solution split into its phases, so each one can be timed on its own.
+/// `Input` may borrow from the puzzle text.
+pub trait Day {
+    type Input<'a>;
+
+    /// Values the day needs besides its input, with defaults for the real input. They are
+    /// handed to `parse_with` and can be overridden per input in `answers.json` or with
+    /// `--param`.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, SolveError>;
+
+    /// `parse` with the values of `PARAMS`. Days with parameters implement this, keep
+    /// what they need in their `Input` and have `parse` call it with the defaults.
+    fn parse_with<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, SolveError> {
+        Self::parse(input)
+    }
+    fn part_one(input: &Self::Input<'_>) -> Result<Solution, SolveError>;
+    fn part_two(input: &Self::Input<'_>) -> Result<Solution, SolveError>;
+
+    /// `part_one`, recording what it does into `stats`. Days with something to report
+    /// implement this and have `part_one` call it with `NoStats`.
+    fn part_one_stats(
+        input: &Self::Input<'_>,
+        _stats: &mut impl Stats,
+    ) -> Result<Solution, SolveError> {
+        Self::part_one(input)
+    }
+
+    /// `part_two`, recording what it does into `stats`, like `part_one_stats`.
+    fn part_two_stats(
+        input: &Self::Input<'_>,
+        _stats: &mut impl Stats,
+    ) -> Result<Solution, SolveError> {
+        Self::part_two(input)
+    }
+
+    /// A valid puzzle input for stress testing, scaled by `size`. Days that have no
+    /// generator keep this default.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Registers a `Day` with the metadata the runner needs, which is what every day module exports.
+pub struct Puzzle<D> {
+    year: u16,
+    day: u8,
+    title: &'static str,
+    test_input: &'static str,
+    solver: PhantomData<fn() -> D>,
+}
+
+impl<D: Day> Puzzle<D> {
+    pub const fn new(year: u16, day: u8, title: &'static str) -> Self {
+        Puzzle {
+            year,
+            day,
+            title,
+            test_input: "test",
+            solver: PhantomData,
+        }
+    }
+
+    pub const fn with_test_input(mut self, name: &'static str) -> Self {
+        self.test_input = name;
+        self
+    }
+
+    fn run(
+        input: &str,
+        params: &Params,
+        [one, two]: &mut [impl Stats; 2],
+    ) -> Result<(SolutionPair, Timings), SolveError> {
+        let time = Instant::now();
+        let parsed = D::parse_with(input, &params.resolve(D::PARAMS))?;
+        let parse = time.elapsed();
+
+        let time = Instant::now();
+        let p1 = D::part_one_stats(&parsed, one)?;
+        let part_one = time.elapsed();
+
+        let time = Instant::now();
+        let p2 = D::part_two_stats(&parsed, two)?;
+        let part_two = time.elapsed();
+
+        let timings = Timings {
+            parse,
+            part_one,
+            part_two,
+        };
+        Ok(((p1, p2), timings))
+    }
+}
+
+impl<D: Day> Solver for Puzzle<D> {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn test_input(&self) -> &'static str {
+        self.test_input
+    }
+
+    fn params(&self) -> &'static [Param] {
+        D::PARAMS
+    }
+
+    fn solve_with(
+        &self,
+        input: &str,
+        params: &Params,
+        stats: bool,
+    ) -> Result<(SolutionPair, Timings, [Recorder; 2]), SolveError> {
+        let mut recorders = [Recorder::default(), Recorder::default()];
+        let solved = if stats {
+            Puzzle::<D>::run(input, params, &mut recorders)
+        } else {
+            Puzzle::<D>::run(input, params, &mut [NoStats, NoStats])
+        };
+        let (solutions, timings) = solved.map_err(|e| e.with_day(self.day))?;
+        Ok((solutions, timings, recorders))
+    }
+
+    fn generate(&self, size: usize, seed: u64) -> Option<String> {
+        D::generate(&mut Rng::new(seed), size)
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Where a solver records named counters and gauges about its own work, like the states
+/// a search explores. Solvers are generic over it, and `NoStats` compiles to nothing, so
+/// instrumentation costs nothing unless the runner asks for it with `--stats`.
+pub trait Stats {
+    /// Adds `n` to the counter `name`.
+    fn count(&mut self, name: &'static str, n: u64);
+
+    /// Sets the gauge `name` to `value`, replacing what it held.
+    fn gauge(&mut self, name: &'static str, value: f64);
+
+    /// The current value of the counter `name`, 0 if nothing was counted.
+    fn counter(&self, name: &'static str) -> u64;
+}
+
+/// Discards everything, what a solver gets when no stats were asked for.
+pub struct NoStats;
+
+impl Stats for NoStats {
+    #[inline(always)]
+    fn count(&mut self, _name: &'static str, _n: u64) {}
+
+    #[inline(always)]
+    fn gauge(&mut self, _name: &'static str, _value: f64) {}
+
+    #[inline(always)]
+    fn counter(&self, _name: &'static str) -> u64 {
+        0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stat {
+    Count(u64),
+    Gauge(f64),
+}
+
+impl Display for Stat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Stat::Count(n) => n.fmt(f),
+            Stat::Gauge(value) => write!(f, "{:.4}", value),
+        }
+    }
+}
+
+/// Keeps every stat in the order it was first recorded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recorder {
+    stats: Vec<(&'static str, Stat)>,
+}
+
+impl Recorder {
+    pub fn stats(&self) -> &[(&'static str, Stat)] {
+        &self.stats
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    fn entry(&mut self, name: &'static str, default: Stat) -> &mut Stat {
+        let index = match self.stats.iter().position(|(n, _)| *n == name) {
+            Some(index) => index,
+            None => {
+                self.stats.push((name, default));
+                self.stats.len() - 1
+            }
+        };
+        &mut self.stats[index].1
+    }
+}
+
+impl Stats for Recorder {
+    fn count(&mut self, name: &'static str, n: u64) {
+        match self.entry(name, Stat::Count(0)) {
+            Stat::Count(total) => *total += n,
+            stat => *stat = Stat::Count(n),
+        }
+    }
+
+    fn gauge(&mut self, name: &'static str, value: f64) {
+        *self.entry(name, Stat::Gauge(value)) = Stat::Gauge(value);
+    }
+
+    fn counter(&self, name: &'static str) -> u64 {
+        match self.stats.iter().find(|(n, _)| *n == name) {
+            Some((_, Stat::Count(n))) => *n,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_add_up_and_gauges_replace() {
+        let mut recorder = Recorder::default();
+        recorder.count("states", 2);
+        recorder.gauge("rate", 0.25);
+        recorder.count("states", 3);
+        recorder.gauge("rate", 0.5);
+
+        assert_eq!(
+            recorder.stats(),
+            [("states", Stat::Count(5)), ("rate", Stat::Gauge(0.5))]
+        );
+        assert_eq!(recorder.counter("states"), 5);
+        assert_eq!(recorder.counter("rate"), 0);
+        assert_eq!(Stat::Gauge(0.5).to_string(), "0.5000");
+    }
+
+    #[test]
+    fn no_stats_records_nothing() {
+        let mut stats = NoStats;
+        stats.count("states", 2);
+        assert_eq!(stats.counter("states"), 0);
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    etc::{
+        params::Params,
+        solver::{millis, Timings},
+    },
+    Solution,
+};
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// A slowdown smaller than this is never a regression, however large it is relative to
+/// the baseline. Days that finish in microseconds jitter by more than any sane threshold.
+const MIN_REGRESSION: Duration = Duration::from_micros(100);
+
+/// One `run` of the command line: the timings and answers of every day that was solved.
+/// The history file holds one of these per line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub label: Option<String>,
+    pub year: u16,
+    /// `real`, the example input's name or the path given with `--input`.
+    pub input: String,
+    pub settings: Settings,
+    pub days: Vec<DayRun>,
+}
+
+/// How a run was made besides its event and input. Only runs with the same settings are
+/// compared: other parameters change the work a day does, and stats cost time.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    /// `--param` overrides as `name=value`, sorted.
+    pub params: Vec<String>,
+    /// The one part reported with `--part`, `None` for both.
+    pub part: Option<u8>,
+    pub stats: bool,
+}
+
+impl Settings {
+    pub fn new(params: &Params, part: Option<u8>, stats: bool) -> Settings {
+        let mut params = params
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+        params.sort();
+        Settings {
+            params,
+            part,
+            stats,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayRun {
+    pub day: u8,
+    pub answers: [Solution; 2],
+    pub parse_ms: f64,
+    pub part_one_ms: f64,
+    pub part_two_ms: f64,
+    pub total_ms: f64,
+}
+
+impl DayRun {
+    pub fn new(day: u8, answers: [Solution; 2], timings: &Timings) -> DayRun {
+        DayRun {
+            day,
+            answers,
+            parse_ms: millis(timings.parse),
+            part_one_ms: millis(timings.part_one),
+            part_two_ms: millis(timings.part_two),
+            total_ms: millis(timings.total()),
+        }
+    }
+}
+
+impl Run {
+    pub fn new(year: u16, input: &str, settings: Settings, label: Option<String>) -> Run {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Run {
+            timestamp,
+            label,
+            year,
+            input: input.to_owned(),
+            settings,
+            days: Vec::new(),
+        }
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayRun> {
+        self.days.iter().find(|d| d.day == day)
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, usize, String),
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            HistoryError::Io(path, e) => write!(f, "could not access {}: {}", path.display(), e),
+            HistoryError::Parse(path, line, message) => {
+                write!(f, "{}:{}: {}", path.display(), line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+pub fn path(input_dir: &str) -> PathBuf {
+    Path::new(input_dir).join(HISTORY_FILE)
+}
+
+/// Every run recorded in `path`, oldest first. A missing file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<Run>, HistoryError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(HistoryError::Io(path.to_path_buf(), e)),
+    };
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| HistoryError::Parse(path.to_path_buf(), i + 1, e.to_string()))
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, run: &Run) -> Result<(), HistoryError> {
+    let line = serde_json::to_string(run).unwrap();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| HistoryError::Io(path.to_path_buf(), e))
+}
+
+/// The runs `run` can be compared with, latest first: those of the same event, input and
+/// settings, and with `label` if one is given.
+pub fn comparable<'a>(
+    history: &'a [Run],
+    run: &'a Run,
+    label: Option<&'a str>,
+) -> impl Iterator<Item = &'a Run> + 'a {
+    history
+        .iter()
+        .rev()
+        .filter(|r| r.year == run.year && r.input == run.input && r.settings == run.settings)
+        .filter(move |r| label.is_none() || r.label.as_deref() == label)
+}
+
+/// What to compare `day` of `run` with: the day in the latest comparable run that solved it.
+pub fn baseline<'a>(
+    history: &'a [Run],
+    run: &'a Run,
+    day: u8,
+    label: Option<&'a str>,
+) -> Option<&'a DayRun> {
+    comparable(history, run, label).find_map(|r| r.day(day))
+}
+
+/// How one day's total time changed against its baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Delta {
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+}
+
+impl Delta {
+    pub fn new(baseline: &DayRun, current: &DayRun) -> Delta {
+        Delta {
+            baseline_ms: baseline.total_ms,
+            current_ms: current.total_ms,
+        }
+    }
+
+    /// Relative change, `0.2` for 20% slower.
+    pub fn change(&self) -> f64 {
+        if self.baseline_ms == 0.0 {
+            return 0.0;
+        }
+        self.current_ms / self.baseline_ms - 1.0
+    }
+
+    /// Slower than the baseline by more than `threshold` (a fraction) and by more
+    /// than `MIN_REGRESSION`.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold && self.current_ms - self.baseline_ms >= millis(MIN_REGRESSION)
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{:.4} ms ({:+.1}%)",
+            self.baseline_ms,
+            self.change() * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_run(day: u8, total_ms: f64) -> DayRun {
+        DayRun {
+            day,
+            answers: [Solution::USize(1), Solution::Unsolved],
+            parse_ms: 0.0,
+            part_one_ms: total_ms,
+            part_two_ms: 0.0,
+            total_ms,
+        }
+    }
+
+    fn run(input: &str, label: Option<&str>, total_ms: f64) -> Run {
+        Run {
+            timestamp: 0,
+            label: label.map(str::to_owned),
+            year: 2022,
+            input: input.to_owned(),
+            settings: Settings::default(),
+            days: vec![day_run(1, total_ms)],
+        }
+    }
+
+    fn baseline_ms(history: &[Run], run: &Run, day: u8, label: Option<&str>) -> Option<f64> {
+        baseline(history, run, day, label).map(|d| d.total_ms)
+    }
+
+    #[test]
+    fn regressions_need_threshold_and_minimum() {
+        let delta = |baseline_ms, current_ms| Delta {
+            baseline_ms,
+            current_ms,
+        };
+
+        assert!(delta(10.0, 12.5).is_regression(0.2));
+        assert!(!delta(10.0, 11.5).is_regression(0.2));
+        assert!(!delta(10.0, 8.0).is_regression(0.2));
+        assert!(!delta(0.01, 0.05).is_regression(0.2));
+        assert_eq!(delta(10.0, 12.5).to_string(), "10.0000 ms (+25.0%)");
+    }
+
+    #[test]
+    fn baseline_matches_input_and_label() {
+        let history = [
+            run("real", Some("fast"), 1.0),
+            run("real", None, 2.0),
+            run("test", None, 3.0),
+        ];
+        let current = run("real", None, 4.0);
+
+        assert_eq!(baseline_ms(&history, &current, 1, None), Some(2.0));
+        assert_eq!(baseline_ms(&history, &current, 1, Some("fast")), Some(1.0));
+        assert_eq!(baseline_ms(&history, &current, 1, Some("slow")), None);
+        assert_eq!(
+            baseline_ms(&history[..1], &run("test", None, 0.0), 1, None),
+            None
+        );
+    }
+
+    #[test]
+    fn baseline_is_the_latest_run_of_the_day() {
+        let mut day_two = run("real", None, 5.0);
+        day_two.days[0].day = 2;
+        let mut failed = run("real", None, 0.0);
+        failed.days.clear();
+        let history = [run("real", None, 1.0), day_two, failed];
+        let current = run("real", None, 4.0);
+
+        assert_eq!(baseline_ms(&history, &current, 1, None), Some(1.0));
+        assert_eq!(baseline_ms(&history, &current, 2, None), Some(5.0));
+        assert_eq!(baseline_ms(&history, &current, 3, None), None);
+    }
+
+    #[test]
+    fn baseline_matches_settings() {
+        let mut sparse = run("real", None, 1.0);
+        sparse.settings = Settings::new(&"store=sparse".parse().unwrap(), None, false);
+        let history = [run("real", None, 2.0), sparse.clone()];
+
+        assert_eq!(
+            baseline_ms(&history, &run("real", None, 3.0), 1, None),
+            Some(2.0)
+        );
+        assert_eq!(baseline_ms(&history, &sparse, 1, None), Some(1.0));
+        let mut stats = run("real", None, 3.0);
+        stats.settings.stats = true;
+        assert_eq!(baseline_ms(&history, &stats, 1, None), None);
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let path = std::env::temp_dir().join(format!("history-{}.jsonl", std::process::id()));
+        assert!(load(&path).unwrap().is_empty());
+
+        let runs = [run("real", Some("v1"), 1.5), run("test", None, 0.25)];
+        for r in &runs {
+            append(&path, r).unwrap();
+        }
+        assert_eq!(load(&path).unwrap(), runs);
+
+        fs::write(&path, "{}\n").unwrap();
+        assert!(matches!(load(&path), Err(HistoryError::Parse(_, 1, _))));
+        fs::remove_file(path).unwrap();
+    }
+}
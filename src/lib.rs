@@ -0,0 +1,32 @@
+//! Advent of Code solutions as a library. The `advent_of_code_template` binary is a thin
+//! command line interface on top of it.
+
+pub mod bench;
+pub mod days;
+pub mod etc;
+pub mod history;
+pub mod output;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod utils;
+pub mod verify;
+pub mod watchdog;
+
+pub use etc::error::SolveError;
+pub use etc::Solution;
+
+pub type SolutionPair = (Solution, Solution);
+
+/// Solves `day` of the latest event for the puzzle text `input`.
+pub fn run(day: u8, input: &str) -> Result<SolutionPair, SolveError> {
+    run_year(registry::latest().year(), day, input)
+}
+
+/// Solves `day` of the event `year` for the puzzle text `input`.
+pub fn run_year(year: u16, day: u8, input: &str) -> Result<SolutionPair, SolveError> {
+    registry::event(year)
+        .and_then(|event| event.solver(day))
+        .ok_or_else(|| SolveError::new(format!("no solver registered in {}", year)).with_day(day))?
+        .solve(input)
+}
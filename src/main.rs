@@ -1,109 +1,529 @@
-mod days;
-mod etc;
+mod args;
 
-use days::{
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+use advent_of_code_template::{
+    etc::answers::{Answers, AnswersError, Verdict},
+    etc::input::{self, STDIN},
+    etc::solver::millis,
+    history::{self, DayRun, Delta, Run, Settings},
+    output::{self, Format, Record},
+    registry::{self, Days, Event},
+    runner::{self, Failure, InputRun, Source},
+    scaffold,
+    verify::{self, Summary},
 };
-use etc::solution::Solution;
+use args::{parse_args, Command, Options};
 use std::env;
-use std::time::Instant;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
 
-pub type SolutionPair = (Solution, Solution);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args).unwrap_or_else(|e| usage_error(&e));
 
-mod utils;
+    if let Command::Help = options.command {
+        println!("{}", args::USAGE);
+        return;
+    }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+    let event = match options.year {
+        Some(year) => registry::event(year)
+            .unwrap_or_else(|| usage_error(&format!("no solvers registered for {}", year))),
+        None => registry::latest(),
+    };
+    if let Command::New = options.command {
+        return new_day(event, options);
+    }
+    let days = event
+        .select(&options.days)
+        .unwrap_or_else(|e| usage_error(&e));
+    for (name, _) in options.params.iter() {
+        if !days.iter().any(|&day| {
+            event
+                .solver(day)
+                .unwrap()
+                .params()
+                .iter()
+                .any(|p| p.name == name)
+        }) {
+            usage_error(&format!("no selected day has a parameter {:?}", name));
+        }
+    }
 
-    let days: Vec<u8> = match args.len() {
-        0..=1 => (1..=25).collect(),
-        2 => {
-            let val = args[1].parse().unwrap();
-            vec![val]
+    match options.command {
+        Command::Run if options.all_inputs => run_all_inputs(event, &days, options),
+        Command::Run => run(event, &days, source(&options), options),
+        Command::Verify => verify(event, &days, options),
+        Command::Bench => bench(event, &days, source(&options), options),
+        Command::Gen => generate(event, days[0], options),
+        Command::New | Command::Help => unreachable!(),
+    }
+}
+
+fn new_day(event: &Event, options: Options) {
+    let day = match &options.days {
+        Days::Only(days) => *days.first().unwrap(),
+        Days::All => unreachable!(),
+    };
+    let title = options.title.as_deref().unwrap_or("TODO");
+
+    match scaffold::new_day(Path::new(event.source_dir), event.input_dir, day, title) {
+        Ok(written) => {
+            for path in written {
+                println!("  · wrote {}", path.display());
+            }
+            println!("Add the example's answers to tests/examples.rs and input/answers.json once it is filled in.");
+        }
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
         }
-        _ => (1..=args.iter().last().unwrap().parse().unwrap()).collect(),
+    }
+}
+
+fn generate(event: &Event, day: u8, options: Options) {
+    let solver = event.solver(day).unwrap();
+    let size = options.size.unwrap();
+    let Some(text) = solver.generate(size, options.seed) else {
+        println!("Error: day {} has no input generator", day);
+        process::exit(1);
+    };
+    let path = input::input_path(
+        event.input_dir,
+        day,
+        &format!("gen-{}-{}", size, options.seed),
+    );
+
+    if let Err(e) = std::fs::write(&path, text) {
+        println!("Error: could not write {}: {}", path.display(), e);
+        process::exit(1);
+    }
+    println!("  · wrote {}", path.display());
+    println!(
+        "Solve it with `cargo run --release -- {} --input {}`.",
+        day,
+        path.display()
+    );
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {}\n\n{}", message, args::USAGE);
+    process::exit(2);
+}
+
+/// Where the selected days read their input from. A single day given no `--input` or
+/// `--test` reads stdin when something is piped in, as in `cat input.txt | aoc 14`.
+/// Empty stdin, like `/dev/null` in a script, is not an input.
+fn source(options: &Options) -> Source {
+    match &options.input {
+        Some(path) => Source::File(path.clone()),
+        None if options.test => Source::Test,
+        None => match piped_input(options) {
+            Some(text) => Source::Text(text),
+            None => Source::Real,
+        },
+    }
+}
+
+fn piped_input(options: &Options) -> Option<String> {
+    let single_day = matches!(&options.days, Days::Only(days) if days.len() == 1);
+    if !single_day || !stdin_is_pipe_or_file() {
+        return None;
+    }
+    input::read_input(Path::new(STDIN))
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Whether stdin is a pipe or a regular file, which end, rather than a terminal, socket or
+/// device that may stay open forever. Checked with `fstat` on a duplicate of the descriptor.
+#[cfg(unix)]
+fn stdin_is_pipe_or_file() -> bool {
+    use std::{fs::File, os::fd::AsFd, os::unix::fs::FileTypeExt};
+
+    let Ok(fd) = io::stdin().as_fd().try_clone_to_owned() else {
+        return false;
+    };
+    File::from(fd)
+        .metadata()
+        .is_ok_and(|meta| meta.file_type().is_fifo() || meta.is_file())
+}
+
+/// Elsewhere stdin is only read when asked for with `--input -`.
+#[cfg(not(unix))]
+fn stdin_is_pipe_or_file() -> bool {
+    false
+}
+
+/// The event's recorded answers, none if there is no `answers.json` or it is malformed.
+fn load_answers(event: &Event, purpose: &str) -> Answers {
+    match Answers::load(event.input_dir) {
+        Ok(answers) => answers,
+        Err(AnswersError::Read(_)) => Answers::default(),
+        Err(e) => {
+            eprintln!("Warning: {}, not {}", e, purpose);
+            Answers::default()
+        }
+    }
+}
+
+/// The thread of a day that timed out cannot be stopped, so the days after it are timed
+/// while it still runs.
+fn warn_still_running(day: u8) {
+    eprintln!(
+        "Warning: day {} is still running in the background, later timings are not compared or recorded",
+        day
+    );
+}
+
+fn report_failures(failed: &[u8]) {
+    if !failed.is_empty() {
+        let days = failed
+            .iter()
+            .map(|d| format!("{:02}", d))
+            .collect::<Vec<_>>();
+        println!("Failed days: {}", days.join(", "));
+        process::exit(1);
+    }
+}
+
+fn verify(event: &Event, days: &[u8], options: Options) {
+    let answers = match Answers::load(event.input_dir) {
+        Ok(answers) => answers,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let checked = verify::verify(event, &answers, days, &options.parts());
+    for day in &checked {
+        println!("\n=== Day {:02}: {} ===", day.day, day.title);
+        for input in &day.inputs {
+            let parts = match &input.parts {
+                Ok(parts) => parts,
+                Err(e) => {
+                    println!("  · {:<6} FAIL: {}", input.name, e);
+                    continue;
+                }
+            };
+            for check in parts {
+                let (name, part, actual) = (&input.name, check.part, check.actual.to_string());
+                match &check.verdict {
+                    Verdict::Pass => println!("  · {:<6} part {}: pass", name, part),
+                    Verdict::Fail(expected) => println!(
+                        "  · {:<6} part {}: FAIL, expected {:?} but got {:?}",
+                        name, part, expected, actual
+                    ),
+                    Verdict::Missing => {
+                        println!("  · {:<6} part {}: missing, got {:?}", name, part, actual)
+                    }
+                    Verdict::Unsolved => println!("  · {:<6} part {}: not implemented", name, part),
+                }
+            }
+        }
+    }
+
+    let summary = Summary::of(&checked);
+    println!(
+        "\nVerified: {} passed, {} failed, {} missing, {} not implemented",
+        summary.passed, summary.failed, summary.missing, summary.unsolved
+    );
+
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// The earlier runs of this event, or none if the history is disabled or unreadable.
+fn load_history(event: &Event, options: &Options) -> Vec<Run> {
+    if !options.history {
+        return Vec::new();
+    }
+    history::load(&history::path(event.input_dir)).unwrap_or_else(|e| {
+        eprintln!("Warning: {}, not comparing with earlier runs", e);
+        Vec::new()
+    })
+}
+
+fn run(event: &Event, days: &[u8], source: Source, options: Options) {
+    let text = options.format == Format::Text;
+    let mut runtime = Duration::ZERO;
+    let mut failed = Vec::new();
+    let mut records = Vec::new();
+
+    // Standard input is neither recorded nor compared, it can be a new input every time.
+    let name = source.history_name();
+    let runs = match name {
+        Some(_) => load_history(event, &options),
+        None => Vec::new(),
     };
+    let settings = Settings::new(&options.params, options.part, options.stats);
+    let mut current = Run::new(
+        event.year(),
+        name.as_deref().unwrap_or_default(),
+        settings,
+        options.label.clone(),
+    );
+    let label = options.baseline.as_deref();
+    if let Some(label) = label {
+        if history::comparable(&runs, &current, Some(label))
+            .next()
+            .is_none()
+        {
+            println!("Error: no run labelled {:?} in the history", label);
+            process::exit(1);
+        }
+    }
+    let mut regressed = Vec::new();
+    let mut timed_out = false;
+    let answers = load_answers(event, "using the parameters recorded for inputs");
+
+    for &day in days {
+        let solver = event.solver(day).unwrap();
+        if text {
+            println!("\n=== Day {:02}: {} ===", day, solver.title());
+        }
+
+        let solved = runner::run_day(
+            event,
+            solver,
+            &source,
+            &answers,
+            &options.params,
+            options.stats,
+            options.timeout(day),
+        );
+
+        let ((p1, p2), timings, [stats_one, stats_two]) = match solved {
+            Ok(solved) => solved,
+            Err(Failure::Error(message)) => {
+                if text {
+                    println!("  · Error: {}", message);
+                }
+                records.push(Record::Error { day, message });
+                failed.push(day);
+                continue;
+            }
+            Err(Failure::Timeout(budget)) => {
+                if text {
+                    println!("  · {} after {:.4} ms", output::TIMEOUT, millis(budget));
+                }
+                records.push(Record::Timeout { day, budget });
+                failed.push(day);
+                if !timed_out {
+                    warn_still_running(day);
+                }
+                timed_out = true;
+                continue;
+            }
+        };
 
-    let mut runtime = 0.0;
-
-    for day in days {
-        let func = get_day_solver(day);
-
-        let time = Instant::now();
-        let input = get_input(day);
-        let (p1, p2) = func(input);
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
-
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
-
-        runtime += elapsed_ms;
-    }
-
-    println!("Total runtime: {:.4} ms", runtime);
-}
-
-fn get_input(day: u8) -> &'static str {
-    match day {
-        1 => include_str!("../input/day01/real.txt"),
-        2 => include_str!("../input/day02/real.txt"),
-        3 => include_str!("../input/day03/real.txt"),
-        4 => include_str!("../input/day04/real.txt"),
-        5 => include_str!("../input/day05/real.txt"),
-        6 => include_str!("../input/day06/real.txt"),
-        7 => include_str!("../input/day07/real.txt"),
-        8 => include_str!("../input/day08/real.txt"),
-        9 => include_str!("../input/day09/real.txt"),
-        10 => include_str!("../input/day10/real.txt"),
-        11 => include_str!("../input/day11/real.txt"),
-        12 => include_str!("../input/day12/real.txt"),
-        13 => include_str!("../input/day13/real.txt"),
-        14 => include_str!("../input/day14/real.txt"),
-        15 => include_str!("../input/day15/real.txt"),
-        16 => include_str!("../input/day16/real.txt"),
-        17 => include_str!("../input/day17/real.txt"),
-        18 => include_str!("../input/day18/real.txt"),
-        19 => include_str!("../input/day19/real.txt"),
-        20 => include_str!("../input/day20/real.txt"),
-        21 => include_str!("../input/day21/real.txt"),
-        22 => include_str!("../input/day22/real.txt"),
-        23 => include_str!("../input/day23/real.txt"),
-        24 => include_str!("../input/day24/real.txt"),
-        25 => include_str!("../input/day25/real.txt"),
-        _ => unreachable!(),
-    }
-}
-
-fn get_day_solver(day: u8) -> fn(&str) -> SolutionPair {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-        _ => unimplemented!(),
+        let day_run = DayRun::new(day, [p1.clone(), p2.clone()], &timings);
+        // Once a day has timed out its thread competes with the next days for the CPU.
+        let delta = history::baseline(&runs, &current, day, label)
+            .filter(|_| !timed_out)
+            .map(|b| Delta::new(b, &day_run));
+        let regression = delta.is_some_and(|d| d.is_regression(options.threshold / 100.0));
+        if regression {
+            regressed.push(day);
+        }
+        current.days.push(day_run);
+
+        let parts = [
+            (1, p1, timings.part_one, stats_one),
+            (2, p2, timings.part_two, stats_two),
+        ]
+        .into_iter()
+        .filter(|(part, _, _, _)| options.parts().contains(part));
+
+        for (part, answer, elapsed, stats) in parts {
+            if text {
+                println!("  · Part {}: {}", part, answer);
+                for (name, value) in stats.stats() {
+                    println!("      {}: {}", name, value);
+                }
+            }
+            records.push(Record::Part {
+                day,
+                part,
+                answer,
+                parse: timings.parse,
+                elapsed,
+            });
+        }
+
+        if text {
+            println!("  · Elapsed: {:.4} ms", millis(timings.total()));
+            if let Some(delta) = delta {
+                let flag = if regression { "  REGRESSION" } else { "" };
+                println!("  · Baseline: {}{}", delta, flag);
+            }
+        }
+        runtime += timings.total();
     }
+
+    // A day that timed out is missing from the run, and the days after it were slowed down.
+    if options.history && name.is_some() && !timed_out && !current.days.is_empty() {
+        if let Err(e) = history::append(&history::path(event.input_dir), &current) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+
+    match options.format {
+        Format::Text => println!("Total runtime: {:.4} ms", millis(runtime)),
+        Format::Json => println!("{}", output::json(&records)),
+        Format::Csv => println!("{}", output::csv(&records)),
+    }
+
+    if !regressed.is_empty() {
+        let against = match &options.baseline {
+            Some(label) => format!("baseline {:?}", label),
+            None => "the previous run".to_owned(),
+        };
+        let days = regressed
+            .iter()
+            .map(|d| format!("{:02}", d))
+            .collect::<Vec<_>>();
+        let message = format!(
+            "Slower than {} by more than {}%: {}",
+            against,
+            options.threshold,
+            days.join(", ")
+        );
+        if text {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    }
+
+    if text {
+        report_failures(&failed);
+    } else if !failed.is_empty() {
+        process::exit(1);
+    }
+    if options.fail_on_regression && !regressed.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs every day against each input file in its directory, labelled by name, and
+/// checks the answers that `answers.json` has for them.
+fn run_all_inputs(event: &Event, days: &[u8], options: Options) {
+    let answers = load_answers(event, "checking answers");
+    let mut failed = Vec::new();
+    let mut timed_out = false;
+
+    for &day in days {
+        let solver = event.solver(day).unwrap();
+        println!("\n=== Day {:02}: {} ===", day, solver.title());
+
+        let runs = runner::run_inputs(
+            event,
+            solver,
+            &answers,
+            &options.params,
+            options.timeout(day),
+        );
+        if runs.is_empty() {
+            println!("  · no inputs");
+        }
+
+        let recorded = answers.inputs(day);
+        let mut ok = true;
+        for (name, run) in runs {
+            let ((p1, p2), timings) = match run {
+                InputRun::Empty => {
+                    println!("  · {:<6} empty, skipped", name);
+                    continue;
+                }
+                InputRun::Failed(Failure::Error(message)) => {
+                    // Some examples are meant to be rejected, only inputs with answers must solve.
+                    println!("  · {:<6} Error: {}", name, message);
+                    ok &= !recorded.contains(&name.as_str());
+                    continue;
+                }
+                InputRun::Failed(Failure::Timeout(budget)) => {
+                    println!(
+                        "  · {:<6} {} after {:.4} ms",
+                        name,
+                        output::TIMEOUT,
+                        millis(budget)
+                    );
+                    if !timed_out {
+                        warn_still_running(day);
+                    }
+                    timed_out = true;
+                    ok = false;
+                    continue;
+                }
+                InputRun::Solved(solutions, timings) => (solutions, timings),
+            };
+
+            for (part, answer) in [(1, p1), (2, p2)] {
+                if !options.parts().contains(&part) {
+                    continue;
+                }
+                let check = match answers.check(day, usize::from(part), &name, &answer) {
+                    Verdict::Pass => " (pass)".to_owned(),
+                    Verdict::Fail(expected) => {
+                        ok = false;
+                        format!(" (FAIL, expected {:?})", expected)
+                    }
+                    Verdict::Missing | Verdict::Unsolved => String::new(),
+                };
+                println!("  · {:<6} Part {}: {}{}", name, part, answer, check);
+            }
+            println!("  · {:<6} Elapsed: {:.4} ms", name, millis(timings.total()));
+        }
+
+        if !ok {
+            failed.push(day);
+        }
+    }
+
+    report_failures(&failed);
+}
+
+fn bench(event: &Event, days: &[u8], source: Source, options: Options) {
+    let mut runtime = Duration::ZERO;
+    let mut failed = Vec::new();
+    let answers = load_answers(event, "using the parameters recorded for inputs");
+
+    for &day in days {
+        let solver = event.solver(day).unwrap();
+        println!("\n=== Day {:02}: {} ===", day, solver.title());
+
+        let report = runner::bench_day(
+            event,
+            solver,
+            &source,
+            &answers,
+            &options.params,
+            options.warmup,
+            options.runs,
+        );
+
+        match report {
+            Ok(report) => {
+                println!("{}", report);
+                runtime += report.total.median;
+            }
+            Err(e) => {
+                println!("  · Error: {}", e);
+                failed.push(day);
+            }
+        }
+    }
+
+    println!(
+        "\nTotal of medians over {} runs ({} warm-up): {:.4} ms",
+        options.runs,
+        options.warmup,
+        millis(runtime)
+    );
+
+    report_failures(&failed);
 }
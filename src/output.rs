@@ -0,0 +1,196 @@
+use std::{str::FromStr, time::Duration};
+
+use serde_json::{json, Value};
+
+use crate::etc::{solver::millis, Solution};
+
+/// How `run` reports its results. `Json` and `Csv` print nothing but the results to stdout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// One row of output: a solved part, or a day that failed or ran out of time before
+/// producing any. `elapsed` is the time spent in that part alone, `parse` the day's shared
+/// parse time.
+pub enum Record {
+    Part {
+        day: u8,
+        part: u8,
+        answer: Solution,
+        parse: Duration,
+        elapsed: Duration,
+    },
+    Error {
+        day: u8,
+        message: String,
+    },
+    Timeout {
+        day: u8,
+        budget: Duration,
+    },
+}
+
+/// What output shows in place of the error of a day that ran out of time.
+pub const TIMEOUT: &str = "TIMEOUT";
+
+const CSV_HEADER: &str = "day,part,answer,type,parse_ms,elapsed_ms,error";
+
+/// The answer as text, `None` for a part that is not implemented.
+fn answer_text(answer: &Solution) -> Option<String> {
+    answer.is_solved().then(|| answer.to_string())
+}
+
+impl Record {
+    fn to_json(&self) -> Value {
+        match self {
+            Record::Part {
+                day,
+                part,
+                answer,
+                parse,
+                elapsed,
+            } => json!({
+                "day": day,
+                "part": part,
+                "answer": answer_text(answer),
+                "type": answer.kind(),
+                "parse_ms": millis(*parse),
+                "elapsed_ms": millis(*elapsed),
+            }),
+            Record::Error { day, message } => json!({
+                "day": day,
+                "error": message,
+            }),
+            Record::Timeout { day, budget } => json!({
+                "day": day,
+                "error": TIMEOUT,
+                "timeout_ms": millis(*budget),
+            }),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        match self {
+            Record::Part {
+                day,
+                part,
+                answer,
+                parse,
+                elapsed,
+            } => format!(
+                "{},{},{},{},{:.4},{:.4},",
+                day,
+                part,
+                answer_text(answer)
+                    .map(|a| csv_field(&a))
+                    .unwrap_or_default(),
+                answer.kind(),
+                millis(*parse),
+                millis(*elapsed)
+            ),
+            Record::Error { day, message } => format!("{},,,,,,{}", day, csv_field(message)),
+            Record::Timeout { day, .. } => format!("{},,,,,,{}", day, TIMEOUT),
+        }
+    }
+}
+
+/// Quotes a field if it holds a separator, a quote or a line break, as RFC 4180 does.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+pub fn json(records: &[Record]) -> String {
+    let records = records.iter().map(Record::to_json).collect::<Vec<_>>();
+    serde_json::to_string_pretty(&records).unwrap()
+}
+
+pub fn csv(records: &[Record]) -> String {
+    let mut lines = vec![CSV_HEADER.to_owned()];
+    lines.extend(records.iter().map(Record::to_csv));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::Part {
+                day: 10,
+                part: 2,
+                answer: Solution::Str("\n#.\n\"x\",".to_owned()),
+                parse: Duration::from_micros(250),
+                elapsed: Duration::from_micros(1500),
+            },
+            Record::Part {
+                day: 16,
+                part: 2,
+                answer: Solution::Unsolved,
+                parse: Duration::from_micros(250),
+                elapsed: Duration::ZERO,
+            },
+            Record::Error {
+                day: 11,
+                message: "day 11: expected a monkey".to_owned(),
+            },
+            Record::Timeout {
+                day: 19,
+                budget: Duration::from_secs(5),
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_quotes_multi_line_answers() {
+        let expected = "day,part,answer,type,parse_ms,elapsed_ms,error\n\
+                        10,2,\"\n#.\n\"\"x\"\",\",Str,0.2500,1.5000,\n\
+                        16,2,,Unsolved,0.2500,0.0000,\n\
+                        11,,,,,,day 11: expected a monkey\n\
+                        19,,,,,,TIMEOUT";
+        assert_eq!(csv(&records()), expected);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let value: Value = serde_json::from_str(&json(&records())).unwrap();
+
+        assert_eq!(value[0]["answer"], "\n#.\n\"x\",");
+        assert_eq!(value[0]["type"], "Str");
+        assert_eq!(value[0]["elapsed_ms"], 1.5);
+        assert_eq!(value[1]["answer"], Value::Null);
+        assert_eq!(value[1]["type"], "Unsolved");
+        assert_eq!(value[2]["error"], "day 11: expected a monkey");
+        assert_eq!(value[3]["error"], "TIMEOUT");
+        assert_eq!(value[3]["timeout_ms"], 5000.0);
+    }
+
+    #[test]
+    fn parses_format() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
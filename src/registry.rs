@@ -0,0 +1,60 @@
+use std::collections::BTreeSet;
+
+use crate::days;
+use crate::etc::solver::Solver;
+
+/// One event year: its solvers, the directory holding its `dayNN` inputs and
+/// the module directory `new` adds days to.
+pub struct Event {
+    pub input_dir: &'static str,
+    pub source_dir: &'static str,
+    pub solvers: &'static [&'static dyn Solver],
+}
+
+/// Which days to run. `All` means every day the chosen event has a solver for.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Days {
+    All,
+    Only(BTreeSet<u8>),
+}
+
+/// Every event the runner knows about. Adding a year means adding its
+/// module next to `days` and listing it here.
+pub static EVENTS: &[Event] = &[Event {
+    input_dir: "input",
+    source_dir: "src/days",
+    solvers: days::PUZZLES,
+}];
+
+pub fn event(year: u16) -> Option<&'static Event> {
+    EVENTS.iter().find(|e| e.year() == year)
+}
+
+pub fn latest() -> &'static Event {
+    EVENTS.iter().max_by_key(|e| e.year()).unwrap()
+}
+
+impl Event {
+    pub fn year(&self) -> u16 {
+        self.solvers[0].year()
+    }
+
+    pub fn solver(&self, day: u8) -> Option<&'static dyn Solver> {
+        self.solvers.iter().copied().find(|s| s.day() == day)
+    }
+
+    /// Resolves a day selection, failing on days this event has no solver for.
+    pub fn select(&self, days: &Days) -> Result<Vec<u8>, String> {
+        match days {
+            Days::All => Ok(self.solvers.iter().map(|s| s.day()).collect()),
+            Days::Only(days) => match days.iter().find(|&&day| self.solver(day).is_none()) {
+                Some(day) => Err(format!(
+                    "no solver registered for day {} of {}",
+                    day,
+                    self.year()
+                )),
+                None => Ok(days.iter().copied().collect()),
+            },
+        }
+    }
+}
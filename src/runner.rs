@@ -0,0 +1,270 @@
+//! Solving days the way the command line does: finding each day's input, the parameters
+//! recorded for it and a time budget to solve it in. The callers decide how to report.
+
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    bench::{self, Report},
+    etc::{
+        answers::Answers,
+        input::{self, REAL_INPUT, STDIN},
+        params::Params,
+        solver::{Solver, Timings},
+        stats::Recorder,
+    },
+    registry::Event,
+    watchdog, SolutionPair,
+};
+
+/// Where a day's input comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// `input/dayNN/real.txt`.
+    Real,
+    /// The day's example input.
+    Test,
+    /// Any file, `-` for stdin.
+    File(PathBuf),
+    /// Text that was already read, like a piped input.
+    Text(String),
+}
+
+impl Source {
+    /// The text of `solver`'s input.
+    pub fn read(&self, event: &Event, solver: &dyn Solver) -> Result<String, String> {
+        let path = match self {
+            Source::Real => input::input_path(event.input_dir, solver.day(), REAL_INPUT),
+            Source::Test => input::input_path(event.input_dir, solver.day(), solver.test_input()),
+            Source::File(path) => path.clone(),
+            Source::Text(text) => return Ok(text.clone()),
+        };
+        input::read_input(&path).map_err(|e| e.to_string())
+    }
+
+    /// The name `answers.json` knows the input by, if it is one of the files in the day's
+    /// input directory.
+    pub fn manifest_name(&self, event: &Event, solver: &dyn Solver) -> Option<String> {
+        match self {
+            Source::Real => Some(REAL_INPUT.to_owned()),
+            Source::Test => Some(solver.test_input().to_owned()),
+            Source::File(path) => {
+                let dir = input::input_path(event.input_dir, solver.day(), REAL_INPUT);
+                let inside = path.parent().is_some_and(|p| Some(p) == dir.parent());
+                inside
+                    .then(|| path.file_stem()?.to_str().map(str::to_owned))
+                    .flatten()
+            }
+            Source::Text(_) => None,
+        }
+    }
+
+    /// The name the input is recorded under in the history. `None` for standard input,
+    /// which may hold a different input every time.
+    pub fn history_name(&self) -> Option<String> {
+        match self {
+            Source::Real => Some(REAL_INPUT.to_owned()),
+            Source::Test => Some("test".to_owned()),
+            Source::File(path) if path.as_os_str() == STDIN => None,
+            Source::File(path) => Some(path.display().to_string()),
+            Source::Text(_) => None,
+        }
+    }
+}
+
+/// The parameters for `solver` on the input `name`: those recorded in `answers.json`,
+/// overridden by `overrides`.
+pub fn params(
+    answers: &Answers,
+    solver: &dyn Solver,
+    name: Option<&str>,
+    overrides: &Params,
+) -> Params {
+    let mut params = name
+        .map(|name| answers.params(solver.day(), name))
+        .unwrap_or_default();
+    params.extend(overrides);
+    params
+}
+
+/// Why a day has no answers to show.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    Error(String),
+    Timeout(Duration),
+}
+
+pub type Solved = (SolutionPair, Timings, [Recorder; 2]);
+
+/// Solves `input` on a worker thread that gets `budget` to finish, with stats if asked for.
+pub fn solve_within_budget(
+    solver: &'static dyn Solver,
+    input: String,
+    params: Params,
+    stats: bool,
+    budget: Option<Duration>,
+) -> Result<Solved, Failure> {
+    let solve = move || solver.solve_with(&input, &params, stats);
+
+    match watchdog::run_with_budget(budget, solve) {
+        Some(solved) => solved.map_err(|e| Failure::Error(e.to_string())),
+        None => Err(Failure::Timeout(budget.unwrap())),
+    }
+}
+
+/// Reads `solver`'s input from `source` and solves it within `budget`.
+pub fn run_day(
+    event: &Event,
+    solver: &'static dyn Solver,
+    source: &Source,
+    answers: &Answers,
+    overrides: &Params,
+    stats: bool,
+    budget: Option<Duration>,
+) -> Result<Solved, Failure> {
+    let params = params(
+        answers,
+        solver,
+        source.manifest_name(event, solver).as_deref(),
+        overrides,
+    );
+    let input = source.read(event, solver).map_err(Failure::Error)?;
+    solve_within_budget(solver, input, params, stats, budget)
+}
+
+/// Reads `solver`'s input from `source` and benchmarks it, see `bench::bench`.
+pub fn bench_day(
+    event: &Event,
+    solver: &dyn Solver,
+    source: &Source,
+    answers: &Answers,
+    overrides: &Params,
+    warmup: usize,
+    runs: usize,
+) -> Result<Report, String> {
+    let params = params(
+        answers,
+        solver,
+        source.manifest_name(event, solver).as_deref(),
+        overrides,
+    );
+    let input = source.read(event, solver)?;
+    bench::bench(solver, &input, &params, warmup, runs).map_err(|e| e.to_string())
+}
+
+/// What became of one of the inputs `run_inputs` found.
+#[derive(Debug)]
+pub enum InputRun {
+    /// The file is blank, like an example that was never filled in.
+    Empty,
+    Failed(Failure),
+    Solved(SolutionPair, Timings),
+}
+
+/// Solves `solver` against every `*.txt` in its input directory, each with the parameters
+/// `answers.json` records for it, in order of name.
+pub fn run_inputs(
+    event: &Event,
+    solver: &'static dyn Solver,
+    answers: &Answers,
+    overrides: &Params,
+    budget: Option<Duration>,
+) -> Vec<(String, InputRun)> {
+    let day = solver.day();
+    input::inputs(event.input_dir, day)
+        .into_iter()
+        .map(|name| {
+            let text = match input::read_input(&input::input_path(event.input_dir, day, &name)) {
+                Ok(text) if text.trim().is_empty() => return (name, InputRun::Empty),
+                Ok(text) => text,
+                Err(e) => return (name, InputRun::Failed(Failure::Error(e.to_string()))),
+            };
+            let params = params(answers, solver, Some(&name), overrides);
+            let run = match solve_within_budget(solver, text, params, false, budget) {
+                Ok((solutions, timings, _)) => InputRun::Solved(solutions, timings),
+                Err(failure) => InputRun::Failed(failure),
+            };
+            (name, run)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry;
+
+    use super::*;
+
+    #[test]
+    fn sources_name_their_inputs() {
+        let event = registry::latest();
+        let solver = event.solver(10).unwrap();
+        let inside = input::input_path(event.input_dir, 10, "gen-30-1");
+
+        assert_eq!(
+            Source::Test.manifest_name(event, solver).as_deref(),
+            Some("test2")
+        );
+        assert_eq!(
+            Source::File(inside.clone())
+                .manifest_name(event, solver)
+                .as_deref(),
+            Some("gen-30-1")
+        );
+        assert_eq!(
+            Source::File("elsewhere/real.txt".into()).manifest_name(event, solver),
+            None
+        );
+        assert_eq!(
+            Source::Text(String::new()).manifest_name(event, solver),
+            None
+        );
+
+        assert_eq!(Source::File(STDIN.into()).history_name(), None);
+        assert_eq!(Source::Text(String::new()).history_name(), None);
+        assert_eq!(
+            Source::File(inside.clone()).history_name(),
+            Some(inside.display().to_string())
+        );
+    }
+
+    #[test]
+    fn runs_take_recorded_params() {
+        let event = registry::latest();
+        let solver = event.solver(15).unwrap();
+        let answers = Answers::load(event.input_dir).unwrap();
+
+        let ((p1, _), _, _) = run_day(
+            event,
+            solver,
+            &Source::Test,
+            &answers,
+            &Params::default(),
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(p1.to_string(), "26");
+
+        // `--param` wins over answers.json, which still sets the size.
+        let overrides = "y=9".parse().unwrap();
+        let (solutions, _, _) = run_day(
+            event,
+            solver,
+            &Source::Test,
+            &answers,
+            &overrides,
+            false,
+            None,
+        )
+        .unwrap();
+        let mut expected = Params::default();
+        expected.set("y", "9");
+        expected.set("size", "20");
+        let text = Source::Test.read(event, solver).unwrap();
+        assert_eq!(
+            solutions,
+            solver.solve_with(&text, &expected, false).unwrap().0
+        );
+        assert_ne!(solutions.0.to_string(), "26");
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::etc::input::{self, REAL_INPUT};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Register(PathBuf, String),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Register(path, message) => write!(f, "{}: {}", path.display(), message),
+            ScaffoldError::Io(path, e) => write!(f, "could not write {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// A solver module for `day` that parses its input into lines and leaves both parts unsolved.
+pub fn template(day: u8, title: &str) -> String {
+    format!(
+        r#"use crate::{{
+    etc::{{
+        error::SolveError,
+        input,
+        solver::{{Day, Puzzle}},
+    }},
+    Solution,
+}};
+
+pub const PUZZLE: Puzzle<Day{day:02}> = Puzzle::new(super::YEAR, {day}, {title:?});
+
+pub struct Day{day:02};
+
+impl Day for Day{day:02} {{
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, SolveError> {{
+        Ok(input::lines(input).collect())
+    }}
+
+    fn part_one(_lines: &Vec<&str>) -> Result<Solution, SolveError> {{
+        Ok(Solution::Unsolved)
+    }}
+
+    fn part_two(_lines: &Vec<&str>) -> Result<Solution, SolveError> {{
+        Ok(Solution::Unsolved)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use crate::etc::{{solver::Solver, Solution}};
+
+    #[test]
+    fn solve() {{
+        let input = include_str!("../../input/day{day:02}/test.txt");
+        let (p1, p2) = super::PUZZLE.solve(input).unwrap();
+
+        assert_eq!(p1, Solution::Unsolved);
+        assert_eq!(p2, Solution::Unsolved);
+    }}
+}}
+"#
+    )
+}
+
+/// Adds `pub mod dayNN;` and `&dayNN::PUZZLE,` to the text of an event's `mod.rs`,
+/// keeping both lists in day order.
+pub fn register(mod_rs: &str, day: u8) -> Result<String, String> {
+    let module = format!("pub mod day{:02};", day);
+    let puzzle = format!("    &day{:02}::PUZZLE,", day);
+
+    let mut lines = mod_rs.lines().map(str::to_owned).collect::<Vec<_>>();
+    if lines.contains(&module) {
+        return Err(format!("day {} is already registered", day));
+    }
+
+    let modules = lines
+        .iter()
+        .filter(|l| l.starts_with("pub mod day"))
+        .count();
+    let at = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod day") && *l > module)
+        .unwrap_or(modules);
+    lines.insert(at, module);
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static PUZZLES"))
+        .ok_or("no `pub static PUZZLES` list")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "];")
+            .ok_or("`PUZZLES` list is not closed by `];`")?;
+    let at = (start + 1..end).find(|&i| lines[i] > puzzle).unwrap_or(end);
+    lines.insert(at, puzzle);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Writes the solver module for `day`, registers it in `source_dir/mod.rs` and creates
+/// empty real and example inputs. Returns the files it created or changed.
+pub fn new_day(
+    source_dir: &Path,
+    input_dir: &str,
+    day: u8,
+    title: &str,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module = source_dir.join(format!("day{:02}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    let mod_rs = source_dir.join("mod.rs");
+    let registered = fs::read_to_string(&mod_rs)
+        .map_err(|e| ScaffoldError::Io(mod_rs.clone(), e))
+        .and_then(|text| {
+            register(&text, day).map_err(|e| ScaffoldError::Register(mod_rs.clone(), e))
+        })?;
+
+    let inputs = [REAL_INPUT, "test"].map(|name| input::input_path(input_dir, day, name));
+    let dir = inputs[0].parent().unwrap();
+    fs::create_dir_all(dir).map_err(|e| ScaffoldError::Io(dir.to_path_buf(), e))?;
+
+    write(&module, &template(day, title))?;
+    write(&mod_rs, &registered)?;
+
+    let mut written = vec![module, mod_rs];
+    for path in inputs {
+        if !path.exists() {
+            write(&path, "")?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "\
+pub mod day01;
+pub mod day03;
+
+use crate::etc::solver::Solver;
+
+pub static PUZZLES: &[&dyn Solver] = &[
+    &day01::PUZZLE,
+    &day03::PUZZLE,
+];
+";
+
+    #[test]
+    fn registers_in_order() {
+        let registered = register(MOD_RS, 2).unwrap();
+        let lines = registered.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[..3],
+            ["pub mod day01;", "pub mod day02;", "pub mod day03;"]
+        );
+        assert_eq!(
+            lines[7..10],
+            [
+                "    &day01::PUZZLE,",
+                "    &day02::PUZZLE,",
+                "    &day03::PUZZLE,"
+            ]
+        );
+
+        let last = register(MOD_RS, 25).unwrap();
+        assert!(last.contains("pub mod day03;\npub mod day25;\n"));
+        assert!(last.contains("    &day03::PUZZLE,\n    &day25::PUZZLE,\n];"));
+    }
+
+    #[test]
+    fn refuses_registered_days() {
+        assert!(register(MOD_RS, 3).is_err());
+        assert!(register("pub mod day01;\n", 2).is_err());
+    }
+
+    #[test]
+    fn creates_a_day() {
+        let root = std::env::temp_dir().join(format!("scaffold-{}", std::process::id()));
+        let source_dir = root.join("days");
+        let input_dir = root.join("input");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(source_dir.join("mod.rs"), MOD_RS).unwrap();
+
+        let written = new_day(
+            &source_dir,
+            input_dir.to_str().unwrap(),
+            2,
+            "Rock Paper Scissors",
+        )
+        .unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(fs::read_to_string(source_dir.join("day02.rs"))
+            .unwrap()
+            .contains(r#"Puzzle<Day02> = Puzzle::new(super::YEAR, 2, "Rock Paper Scissors");"#));
+        assert_eq!(
+            fs::read_to_string(input_dir.join("day02").join("test.txt")).unwrap(),
+            ""
+        );
+
+        assert!(matches!(
+            new_day(&source_dir, input_dir.to_str().unwrap(), 2, ""),
+            Err(ScaffoldError::Exists(_))
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,2 +1,10 @@
+pub mod coordinate;
+pub mod cuboid;
+pub mod grid;
+pub mod grid_store;
+#[cfg(test)]
+pub mod prop;
+pub mod rng;
 pub mod vector_2d;
-pub mod vector_3d;
\ No newline at end of file
+pub mod vector_3d;
+pub mod voxels;
@@ -0,0 +1,89 @@
+//! The signed integers `Vector2`, `Vector3` and `Cuboid` are generic over. Conversions
+//! to and from `usize` are checked instead of cast with `as`.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A signed integer that vectors can use for their coordinates.
+pub trait Coordinate:
+    Copy
+    + Debug
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + TryFrom<usize>
+{
+    /// `self` as an index, `usize::MAX` if it is negative or too large, so it
+    /// lies outside anything it indexes.
+    fn to_usize(self) -> usize;
+
+    /// `n` as a coordinate. Panics if it does not fit.
+    fn from_usize(n: usize) -> Self {
+        match Self::try_from(n) {
+            Ok(coordinate) => coordinate,
+            Err(_) => panic!("{} does not fit in {}", n, std::any::type_name::<Self>()),
+        }
+    }
+
+    /// `|self|`, `None` if it does not fit in a `usize`.
+    fn abs_usize(self) -> Option<usize>;
+
+    /// `|self - other|`, `None` if it does not fit in a `usize`.
+    fn abs_diff_usize(self, other: Self) -> Option<usize>;
+
+    /// `self + other`, `None` if it overflows.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// `self - other`, `None` if it overflows.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! coordinate {
+    ($($t:ty)*) => {$(
+        impl Coordinate for $t {
+            fn to_usize(self) -> usize {
+                usize::try_from(self).unwrap_or(usize::MAX)
+            }
+
+            fn abs_usize(self) -> Option<usize> {
+                usize::try_from(self.unsigned_abs()).ok()
+            }
+
+            fn abs_diff_usize(self, other: Self) -> Option<usize> {
+                usize::try_from(self.abs_diff(other)).ok()
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+        }
+    )*};
+}
+
+coordinate!(i8 i16 i32 i64 i128 isize);
+
+/// The sum of the distances along each axis. Panics if one of them, or the sum, does not
+/// fit in a `usize`.
+pub fn distance<const N: usize>(axes: [Option<usize>; N]) -> usize {
+    match axes
+        .into_iter()
+        .try_fold(0usize, |sum, axis| sum.checked_add(axis?))
+    {
+        Some(distance) => distance,
+        None => panic!("distance {:?} does not fit in a usize", axes),
+    }
+}
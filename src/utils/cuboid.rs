@@ -0,0 +1,188 @@
+//! Inclusive 3D boxes. `expand`, `checked_size` and `checked_volume` return `None`
+//! instead of overflowing.
+
+use super::{coordinate::Coordinate, vector_3d::Vector3};
+
+/// An axis-aligned box of points, `min` and `max` included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<T = isize> {
+    pub min: Vector3<T>,
+    pub max: Vector3<T>,
+}
+
+impl<T: Coordinate> Cuboid<T> {
+    /// The box between two opposite corners, in any order.
+    pub fn new(a: Vector3<T>, b: Vector3<T>) -> Cuboid<T> {
+        Cuboid {
+            min: Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// The smallest box holding every point, `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vector3<T>>) -> Option<Cuboid<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Cuboid::new(first, first), |cuboid, p| cuboid.including(p)))
+    }
+
+    /// The smallest box holding this one and `point`.
+    pub fn including(self, point: Vector3<T>) -> Cuboid<T> {
+        Cuboid {
+            min: Vector3::new(
+                self.min.x.min(point.x),
+                self.min.y.min(point.y),
+                self.min.z.min(point.z),
+            ),
+            max: Vector3::new(
+                self.max.x.max(point.x),
+                self.max.y.max(point.y),
+                self.max.z.max(point.z),
+            ),
+        }
+    }
+
+    /// The box grown by `by` on every side, `None` if a corner no longer fits in `T`.
+    pub fn expand(self, by: T) -> Option<Cuboid<T>> {
+        let grow = |min: T, max: T| Some((min.checked_sub(by)?, max.checked_add(by)?));
+        let (min_x, max_x) = grow(self.min.x, self.max.x)?;
+        let (min_y, max_y) = grow(self.min.y, self.max.y)?;
+        let (min_z, max_z) = grow(self.min.z, self.max.z)?;
+        Some(Cuboid {
+            min: Vector3::new(min_x, min_y, min_z),
+            max: Vector3::new(max_x, max_y, max_z),
+        })
+    }
+
+    pub fn contains(&self, point: Vector3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// How many points the box spans along each axis, `None` if one of them does not fit
+    /// in a `usize`.
+    pub fn checked_size(&self) -> Option<[usize; 3]> {
+        let span = |min: T, max: T| max.abs_diff_usize(min)?.checked_add(1);
+        Some([
+            span(self.min.x, self.max.x)?,
+            span(self.min.y, self.max.y)?,
+            span(self.min.z, self.max.z)?,
+        ])
+    }
+
+    /// How many points the box holds, `None` if that does not fit in a `usize`.
+    pub fn checked_volume(&self) -> Option<usize> {
+        self.checked_size()?
+            .into_iter()
+            .try_fold(1usize, usize::checked_mul)
+    }
+
+    /// How many points the box spans along each axis. Panics if one of them does not fit
+    /// in a `usize`.
+    pub fn size(&self) -> [usize; 3] {
+        match self.checked_size() {
+            Some(size) => size,
+            None => panic!("the size of {:?} does not fit in a usize", self),
+        }
+    }
+
+    /// How many points the box holds. Panics if that does not fit in a `usize`.
+    pub fn volume(&self) -> usize {
+        match self.checked_volume() {
+            Some(volume) => volume,
+            None => panic!("the volume of {:?} does not fit in a usize", self),
+        }
+    }
+
+    /// Where `point` comes in `points`, `None` outside the box.
+    pub fn index_of(&self, point: Vector3<T>) -> Option<usize> {
+        if !self.contains(point) {
+            return None;
+        }
+        let [width, height, _] = self.size();
+        let offset = |min: T, p: T| p.abs_diff_usize(min);
+        let (x, y, z) = (
+            offset(self.min.x, point.x)?,
+            offset(self.min.y, point.y)?,
+            offset(self.min.z, point.z)?,
+        );
+        Some(x + width * (y + height * z))
+    }
+
+    /// The point at `index` of `points`.
+    pub fn point_at(&self, index: usize) -> Vector3<T> {
+        let [width, height, _] = self.size();
+        let offset = Vector3::new(
+            T::from_usize(index % width),
+            T::from_usize(index / width % height),
+            T::from_usize(index / width / height),
+        );
+        self.min + offset
+    }
+
+    /// Every point in the box, `x` changing fastest and `z` slowest.
+    pub fn points(&self) -> impl Iterator<Item = Vector3<T>> + '_ {
+        (0..self.volume()).map(|i| self.point_at(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_of_points() {
+        let points = [
+            Vector3::new(1, 5, -2),
+            Vector3::new(3, 2, 0),
+            Vector3::new(2, 2, 2),
+        ];
+        let cuboid = Cuboid::<i32>::from_points(points).unwrap();
+
+        assert_eq!(
+            cuboid,
+            Cuboid::new(Vector3::new(3, 5, 2), Vector3::new(1, 2, -2))
+        );
+        assert_eq!(cuboid.size(), [3, 4, 5]);
+        assert!(points.iter().all(|&p| cuboid.contains(p)));
+        assert!(!cuboid.contains(Vector3::new(0, 3, 0)));
+        assert!(cuboid.expand(1).unwrap().contains(Vector3::new(0, 3, 0)));
+        assert_eq!(Cuboid::<i32>::from_points([]), None);
+    }
+
+    #[test]
+    fn extremes_do_not_overflow() {
+        let widest = Cuboid::<i32>::new(Vector3::new(i32::MIN, 0, 0), Vector3::new(i32::MAX, 0, 0));
+        assert_eq!(widest.size(), [1 << 32, 1, 1]);
+        assert_eq!(widest.expand(1), None);
+        assert_eq!(
+            Cuboid::<i32>::new(Vector3::default(), Vector3::default())
+                .expand(1)
+                .unwrap()
+                .volume(),
+            27
+        );
+
+        let huge = Cuboid::<i64>::new(
+            Vector3::new(0, 0, 0),
+            Vector3::new(1 << 30, 1 << 30, 1 << 30),
+        );
+        assert_eq!(huge.checked_volume(), None);
+        let whole = Cuboid::<i64>::new(Vector3::new(i64::MIN, 0, 0), Vector3::new(i64::MAX, 0, 0));
+        assert_eq!(whole.checked_size(), None);
+    }
+
+    #[test]
+    fn points_and_indices_agree() {
+        let cuboid = Cuboid::<i64>::new(Vector3::new(-1, 0, 4), Vector3::new(1, 1, 5));
+        let points = cuboid.points().collect::<Vec<_>>();
+
+        assert_eq!(points.len(), cuboid.volume());
+        assert_eq!(points[..2], [Vector3::new(-1, 0, 4), Vector3::new(0, 0, 4)]);
+        for (i, &p) in points.iter().enumerate() {
+            assert_eq!(cuboid.index_of(p), Some(i));
+        }
+        assert_eq!(cuboid.index_of(Vector3::new(2, 0, 4)), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! Fixed-size maps read from the puzzle text. `Grid::parse` turns each character into a
+//! cell and points at the first one it rejects, `parse_padded` accepts ragged rows, and
+//! `Display` draws the grid back as text. Days 8, 12, 22, 23 and 24 use it.
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::{Index, IndexMut},
+};
+
+use crate::etc::{error::SolveError, input};
+
+use super::vector_2d::Vector2;
+
+const NEIGHBOURS_4: [Vector2; 4] = [
+    Vector2 { x: 0, y: -1 },
+    Vector2 { x: 1, y: 0 },
+    Vector2 { x: 0, y: 1 },
+    Vector2 { x: -1, y: 0 },
+];
+
+const NEIGHBOURS_8: [Vector2; 8] = [
+    Vector2 { x: -1, y: -1 },
+    Vector2 { x: 0, y: -1 },
+    Vector2 { x: 1, y: -1 },
+    Vector2 { x: 1, y: 0 },
+    Vector2 { x: 1, y: 1 },
+    Vector2 { x: 0, y: 1 },
+    Vector2 { x: -1, y: 1 },
+    Vector2 { x: -1, y: 0 },
+];
+
+/// A rectangle of cells stored row by row. Positions are `Vector2`s with `x` the column
+/// and `y` the row, counted from the top left like the lines of the puzzle text, so steps
+/// on it use `YAxis::Down`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// One cell per character of `input`, mapped by `cell`. Every row has to be as wide
+    /// as the first, and a character `cell` has no value for is reported where it is.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, SolveError> {
+        let width = input::lines(input)
+            .next()
+            .map_or(0, |line| line.chars().count());
+        if let Some(line) = input::lines(input).find(|l| l.chars().count() != width) {
+            return Err(SolveError::at(
+                input,
+                line,
+                "expected every row to be the same width",
+            ));
+        }
+        Grid::parse_rows(input, width, || unreachable!(), &mut cell)
+    }
+
+    /// Like `parse`, for rows of different widths: the grid is as wide as the widest and
+    /// shorter rows are filled up with `pad`.
+    pub fn parse_padded(
+        input: &str,
+        pad: T,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, SolveError>
+    where
+        T: Clone,
+    {
+        let width = input::lines(input)
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or_default();
+        Grid::parse_rows(input, width, || pad.clone(), &mut cell)
+    }
+
+    fn parse_rows(
+        input: &str,
+        width: usize,
+        mut pad: impl FnMut() -> T,
+        cell: &mut impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, SolveError> {
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for line in input::lines(input) {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    SolveError::at(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        format!("unexpected {:?}", c),
+                    )
+                })?;
+                cells.push(value);
+            }
+            while cells.len() - start < width {
+                cells.push(pad());
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Vector2) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    fn index_of(&self, position: Vector2) -> Option<usize> {
+        let x = usize::try_from(position.x)
+            .ok()
+            .filter(|&x| x < self.width)?;
+        let y = usize::try_from(position.y)
+            .ok()
+            .filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn position_of(&self, index: usize) -> Vector2 {
+        Vector2::new_usize(index % self.width, index / self.width)
+    }
+
+    /// The cell at `position`, `None` outside the grid.
+    pub fn get(&self, position: Vector2) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Vector2) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vector2> + '_ {
+        (0..self.cells.len()).map(|i| self.position_of(i))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// The positions above, right of, below and left of `position` that are in the grid.
+    pub fn neighbours(&self, position: Vector2) -> impl Iterator<Item = Vector2> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .map(move |&d| position + d)
+            .filter(|&p| self.contains(p))
+    }
+
+    /// Like `neighbours`, diagonals included.
+    pub fn neighbours_diagonal(&self, position: Vector2) -> impl Iterator<Item = Vector2> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .map(move |&d| position + d)
+            .filter(|&p| self.contains(p))
+    }
+
+    /// The cells of row `y`, left to right. Panics if there is no such row.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            y < self.height,
+            "row {} is outside a grid of height {}",
+            y,
+            self.height
+        );
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if there is no such column.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} is outside a grid of width {}",
+            x,
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// The cells seen from `from` looking in `step`, `from` itself excluded, up to the
+    /// edge of the grid.
+    pub fn line_of_sight(
+        &self,
+        from: Vector2,
+        step: Vector2,
+    ) -> impl Iterator<Item = (Vector2, &T)> {
+        let mut position = from;
+        std::iter::from_fn(move || {
+            if step == Vector2::default() {
+                return None;
+            }
+            position += step;
+            self.get(position).map(|cell| (position, cell))
+        })
+    }
+
+    /// The first position holding `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Vector2>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Every position holding `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Vector2> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(p, _)| p)
+    }
+
+    /// Adds rows filled with `fill` at the bottom until the grid is `height` rows high.
+    pub fn grow(&mut self, height: usize, fill: T)
+    where
+        T: Clone,
+    {
+        if height > self.height {
+            self.cells.resize(height * self.width, fill);
+            self.height = height;
+        }
+    }
+}
+
+impl<T> Index<Vector2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vector2) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside a {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Vector2> for Grid<T> {
+    fn index_mut(&mut self, position: Vector2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", position, width, height),
+        }
+    }
+}
+
+/// Draws the grid as its puzzle text, each cell with its own `Display`.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, SolveError> {
+        Grid::parse(input, |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = digits("123\n456\r\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Vector2 { x: 2, y: 0 }), Some(&3));
+        assert_eq!(grid[Vector2 { x: 0, y: 1 }], 4);
+        assert_eq!(grid.get(Vector2 { x: 3, y: 0 }), None);
+        assert_eq!(grid.get(Vector2 { x: -1, y: 1 }), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_input() {
+        let e = digits("123\n4x6").unwrap_err();
+        assert_eq!(
+            (e.message.as_str(), e.location.unwrap().column),
+            ("unexpected 'x'", 2)
+        );
+
+        let e = digits("123\n45").unwrap_err();
+        assert_eq!(e.message, "expected every row to be the same width");
+
+        let grid = Grid::parse_padded("  1\n2", 0, |c| c.to_digit(10).or(Some(0))).unwrap();
+        assert_eq!(grid.to_string(), "001\n200");
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = digits("123\n456\n789").unwrap();
+        let values = |ps: Vec<Vector2>| ps.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+
+        assert_eq!(
+            values(grid.neighbours(Vector2 { x: 0, y: 0 }).collect()),
+            [2, 4]
+        );
+        assert_eq!(
+            values(grid.neighbours(Vector2 { x: 1, y: 1 }).collect()),
+            [2, 6, 8, 4]
+        );
+        assert_eq!(grid.neighbours_diagonal(Vector2 { x: 1, y: 1 }).count(), 8);
+        assert_eq!(
+            values(grid.neighbours_diagonal(Vector2 { x: 2, y: 2 }).collect()),
+            [5, 6, 8]
+        );
+    }
+
+    #[test]
+    fn rows_columns_and_sight_lines() {
+        let mut grid = digits("123\n456\n789").unwrap();
+
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+        let sight = grid.line_of_sight(Vector2 { x: 2, y: 2 }, Vector2 { x: -1, y: -1 });
+        assert_eq!(sight.map(|(_, &v)| v).collect::<Vec<_>>(), [5, 1]);
+        assert_eq!(
+            grid.line_of_sight(Vector2 { x: 0, y: 0 }, Vector2::default())
+                .count(),
+            0
+        );
+
+        assert_eq!(grid.find(&5), Some(Vector2 { x: 1, y: 1 }));
+        grid[Vector2 { x: 0, y: 2 }] = 5;
+        assert_eq!(grid.find_all(&5).count(), 2);
+        assert_eq!(grid.find(&0), None);
+
+        grid.grow(4, 0);
+        assert_eq!(grid.to_string(), "123\n456\n589\n000");
+    }
+}
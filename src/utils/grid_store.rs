@@ -0,0 +1,385 @@
+//! Maps whose extent is not known up front, like the sand of day 14, the rock tower of
+//! day 17 and the elves of day 23. Those days pick `DenseGrid` or `SparseGrid` with their
+//! `store` parameter, so `cargo run --release -- bench 14 --param store=sparse` compares
+//! the two.
+
+use std::{collections::HashMap, str::FromStr};
+
+use super::vector_2d::Vector2;
+use crate::etc::error::SolveError;
+
+/// Values at points of an unbounded plane, for maps whose extent is not known up front.
+/// `DenseGrid` and `SparseGrid` both implement it, so a day written against the trait can
+/// be run and benchmarked with either.
+pub trait GridStore<V>: Default {
+    fn get(&self, position: Vector2) -> Option<&V>;
+
+    fn get_mut(&mut self, position: Vector2) -> Option<&mut V>;
+
+    /// Sets the value at `position`, returning the one it replaces, or an error if the
+    /// store cannot grow to hold `position`.
+    fn try_insert(&mut self, position: Vector2, value: V) -> Result<Option<V>, SolveError>;
+
+    /// Like `try_insert`, for maps that are known to stay small. Panics if the store
+    /// cannot grow to hold `position`.
+    fn insert(&mut self, position: Vector2, value: V) -> Option<V> {
+        match self.try_insert(position, value) {
+            Ok(old) => old,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    fn remove(&mut self, position: Vector2) -> Option<V>;
+
+    /// How many positions hold a value.
+    fn len(&self) -> usize;
+
+    /// Every position holding a value, in no particular order.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Vector2, &'a V)>
+    where
+        V: 'a;
+
+    /// Removes every value, keeping the memory for reuse.
+    fn clear(&mut self);
+
+    fn contains(&self, position: Vector2) -> bool {
+        self.get(position).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The value at `position`, inserting `default()` first if there is none.
+    fn get_or_insert_with(&mut self, position: Vector2, default: impl FnOnce() -> V) -> &mut V {
+        if !self.contains(position) {
+            self.insert(position, default());
+        }
+        self.get_mut(position).unwrap()
+    }
+}
+
+/// Which `GridStore` a day keeps its map in, chosen with a `store` parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StoreKind {
+    #[default]
+    Dense,
+    Sparse,
+}
+
+impl FromStr for StoreKind {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<StoreKind, String> {
+        match text {
+            "dense" => Ok(StoreKind::Dense),
+            "sparse" => Ok(StoreKind::Sparse),
+            _ => Err(format!("expected dense or sparse, not {:?}", text)),
+        }
+    }
+}
+
+/// A `GridStore` in one `Vec` covering the rectangle from `min` to `min + (width, height)`,
+/// which grows in whichever direction a value lands outside it. Each growth at least
+/// doubles the side it extends, so filling a region costs amortised constant time per cell.
+/// A value too far away to keep the rectangle within `MAX_AREA` cells is refused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseGrid<V> {
+    min: Vector2,
+    width: usize,
+    height: usize,
+    cells: Vec<Option<V>>,
+    len: usize,
+}
+
+impl<V> Default for DenseGrid<V> {
+    fn default() -> Self {
+        DenseGrid {
+            min: Vector2::default(),
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<V> DenseGrid<V> {
+    /// The most cells the rectangle may cover. Maps with points this far apart belong in a
+    /// `SparseGrid`.
+    pub const MAX_AREA: usize = 1 << 28;
+
+    /// The position of the first cell past the covered rectangle, along both axes.
+    fn end(&self) -> Vector2 {
+        self.min + Vector2::new_usize(self.width, self.height)
+    }
+
+    fn index_of(&self, position: Vector2) -> Option<usize> {
+        let offset = |p: isize, min: isize| usize::try_from(p.checked_sub(min)?).ok();
+        let x = offset(position.x, self.min.x).filter(|&x| x < self.width)?;
+        let y = offset(position.y, self.min.y).filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn position_of(&self, index: usize) -> Vector2 {
+        self.min + Vector2::new_usize(index % self.width, index / self.width)
+    }
+
+    /// The corners of the covered rectangle grown to hold `position`, each side it extends
+    /// at least doubled if `double`. `None` if a corner does not fit in an `isize`.
+    fn grown(&self, position: Vector2, double: bool) -> Option<(Vector2, Vector2)> {
+        let after = Vector2::new(position.x.checked_add(1)?, position.y.checked_add(1)?);
+        if self.cells.is_empty() {
+            return Some((position, after));
+        }
+        let (mut min, mut end) = (self.min, self.end());
+        let (width, height) = match double {
+            true => (self.width as isize, self.height as isize),
+            false => (0, 0),
+        };
+        if position.x < min.x {
+            min.x = position.x.min(min.x.saturating_sub(width));
+        } else if position.x >= end.x {
+            end.x = after.x.max(end.x.saturating_add(width));
+        }
+        if position.y < min.y {
+            min.y = position.y.min(min.y.saturating_sub(height));
+        } else if position.y >= end.y {
+            end.y = after.y.max(end.y.saturating_add(height));
+        }
+        Some((min, end))
+    }
+
+    /// Grows the covered rectangle until it holds `position`, by as little as it takes if
+    /// doubling a side would make it larger than `MAX_AREA`.
+    fn reserve(&mut self, position: Vector2) -> Result<(), SolveError> {
+        let area = |(min, end): (Vector2, Vector2)| {
+            let size = (end.x.abs_diff(min.x), end.y.abs_diff(min.y));
+            size.0
+                .checked_mul(size.1)
+                .filter(|&area| area <= Self::MAX_AREA)
+                .map(|_| (min, size))
+        };
+        let (min, (width, height)) = [true, false]
+            .into_iter()
+            .find_map(|double| self.grown(position, double).and_then(area))
+            .ok_or_else(|| {
+                SolveError::new(format!(
+                    "{:?} is too far from the rest of the map to keep it in a DenseGrid",
+                    position
+                ))
+            })?;
+
+        let mut grown = DenseGrid {
+            min,
+            width,
+            height,
+            cells: Vec::new(),
+            len: self.len,
+        };
+        grown.cells.resize_with(width * height, || None);
+        for (i, cell) in std::mem::take(&mut self.cells).into_iter().enumerate() {
+            if cell.is_some() {
+                let index = grown.index_of(self.position_of(i)).unwrap();
+                grown.cells[index] = cell;
+            }
+        }
+        *self = grown;
+        Ok(())
+    }
+}
+
+impl<V> GridStore<V> for DenseGrid<V> {
+    fn get(&self, position: Vector2) -> Option<&V> {
+        self.index_of(position).and_then(|i| self.cells[i].as_ref())
+    }
+
+    fn get_mut(&mut self, position: Vector2) -> Option<&mut V> {
+        self.index_of(position).and_then(|i| self.cells[i].as_mut())
+    }
+
+    fn try_insert(&mut self, position: Vector2, value: V) -> Result<Option<V>, SolveError> {
+        let index = match self.index_of(position) {
+            Some(index) => index,
+            None => {
+                self.reserve(position)?;
+                self.index_of(position).unwrap()
+            }
+        };
+        let old = self.cells[index].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        Ok(old)
+    }
+
+    fn remove(&mut self, position: Vector2) -> Option<V> {
+        let old = self.index_of(position).and_then(|i| self.cells[i].take());
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Vector2, &'a V)>
+    where
+        V: 'a,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| cell.as_ref().map(|value| (self.position_of(i), value)))
+    }
+
+    fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| *cell = None);
+        self.len = 0;
+    }
+}
+
+/// A `GridStore` over a `HashMap`, which only pays for the positions that hold a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<V> {
+    cells: HashMap<Vector2, V>,
+}
+
+impl<V> Default for SparseGrid<V> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<V> GridStore<V> for SparseGrid<V> {
+    fn get(&self, position: Vector2) -> Option<&V> {
+        self.cells.get(&position)
+    }
+
+    fn get_mut(&mut self, position: Vector2) -> Option<&mut V> {
+        self.cells.get_mut(&position)
+    }
+
+    fn try_insert(&mut self, position: Vector2, value: V) -> Result<Option<V>, SolveError> {
+        Ok(self.cells.insert(position, value))
+    }
+
+    fn remove(&mut self, position: Vector2) -> Option<V> {
+        self.cells.remove(&position)
+    }
+
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Vector2, &'a V)>
+    where
+        V: 'a,
+    {
+        self.cells
+            .iter()
+            .map(|(&position, value)| (position, value))
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    fn get_or_insert_with(&mut self, position: Vector2, default: impl FnOnce() -> V) -> &mut V {
+        self.cells.entry(position).or_insert_with(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values spread around the origin in every direction, so a `DenseGrid` has to grow
+    /// both ways along both axes.
+    fn fill<S: GridStore<isize>>() -> S {
+        let mut store = S::default();
+        for (i, (x, y)) in [(0, 0), (3, -2), (-5, 1), (2, 7), (-1, -9), (3, -2)]
+            .into_iter()
+            .enumerate()
+        {
+            store.insert(Vector2::new(x, y), i as isize);
+        }
+        store
+    }
+
+    fn check<S: GridStore<isize>>() {
+        let mut store = fill::<S>();
+
+        assert_eq!(store.len(), 5);
+        assert_eq!(store.get(Vector2::new(3, -2)), Some(&5));
+        assert_eq!(store.get(Vector2::new(-5, 1)), Some(&2));
+        assert!(!store.contains(Vector2::new(1, 1)));
+        assert!(!store.contains(Vector2::new(100, -100)));
+
+        *store.get_or_insert_with(Vector2::new(1, 1), || 10) += 1;
+        *store.get_or_insert_with(Vector2::new(1, 1), || 10) += 1;
+        assert_eq!(store.get(Vector2::new(1, 1)), Some(&12));
+
+        assert_eq!(store.remove(Vector2::new(0, 0)), Some(0));
+        assert_eq!(store.remove(Vector2::new(0, 0)), None);
+        let mut values = store
+            .iter()
+            .map(|(p, &v)| (p.x, p.y, v))
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(
+            values,
+            [(-5, 1, 2), (-1, -9, 4), (1, 1, 12), (2, 7, 3), (3, -2, 5)]
+        );
+
+        store.clear();
+        assert!(store.is_empty());
+        assert_eq!(store.iter().count(), 0);
+    }
+
+    #[test]
+    fn dense_grid() {
+        check::<DenseGrid<isize>>();
+    }
+
+    #[test]
+    fn sparse_grid() {
+        check::<SparseGrid<isize>>();
+    }
+
+    #[test]
+    fn dense_grid_grows_geometrically() {
+        let mut store = DenseGrid::default();
+        for x in 0..1000 {
+            store.insert(Vector2::new(-x, x), ());
+        }
+
+        assert_eq!(store.len(), 1000);
+        assert!(store.width < 2048 && store.height < 2048);
+    }
+
+    #[test]
+    fn dense_grid_refuses_far_points() {
+        let mut store = DenseGrid::default();
+        store.insert(Vector2::new(0, 0), 1);
+
+        let far = Vector2::new(90_000_000_000, 3);
+        assert!(store.try_insert(far, 2).is_err());
+        assert!(store.try_insert(Vector2::new(isize::MAX, 0), 2).is_err());
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get(Vector2::new(0, 0)), Some(&1));
+
+        let mut sparse = SparseGrid::default();
+        assert_eq!(sparse.try_insert(far, 2), Ok(None));
+    }
+
+    #[test]
+    fn store_kinds() {
+        assert_eq!("sparse".parse(), Ok(StoreKind::Sparse));
+        assert!("hash".parse::<StoreKind>().is_err());
+    }
+}
@@ -0,0 +1,32 @@
+//! A minimal property-testing helper: run a property against many generated values
+//! and report the seed of the first one that fails, so it can be replayed.
+
+use std::{env, fmt::Debug};
+
+use crate::utils::rng::Rng;
+
+/// Number of cases `check` runs, unless `PROP_CASES` says otherwise.
+const CASES: u64 = 256;
+
+fn env_number(name: &str) -> Option<u64> {
+    env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+/// Checks `property` against values made by `generate`. Case `i` is generated from seed
+/// `PROP_SEED + i` (`PROP_SEED` defaults to 0), and a failure panics with that seed and
+/// the value, so `PROP_SEED=<seed> PROP_CASES=1` reproduces it.
+pub fn check<T: Debug>(generate: impl Fn(&mut Rng) -> T, property: impl Fn(&T) -> bool) {
+    let base = env_number("PROP_SEED").unwrap_or(0);
+    let cases = env_number("PROP_CASES").unwrap_or(CASES);
+
+    for i in 0..cases {
+        let seed = base.wrapping_add(i);
+        let value = generate(&mut Rng::new(seed));
+        assert!(
+            property(&value),
+            "property failed for seed {}: {:?}",
+            seed,
+            value
+        );
+    }
+}
@@ -1,68 +1,217 @@
+//! Points, offsets and headings in the plane. `Direction` is one of the eight compass
+//! points, parsed from `U/D/L/R`, `^v<>` or `N/E/S/W`, and `unit` turns it into a step
+//! once a `YAxis` says which way `y` grows. Maps read from the puzzle text, `Grid`
+//! included, count rows down from the top and use `YAxis::Down`; the `N`/`E`/`S`/`W`
+//! constants use `YAxis::Up`, with north at larger `y`.
+
 #![allow(dead_code)]
 
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::{
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use super::coordinate::{distance, Coordinate};
 
+// These point the `YAxis::Up` way, like `Direction::N.unit(YAxis::Up)`.
 pub const N: Vector2 = Vector2 { x: 0, y: 1 };
 pub const E: Vector2 = Vector2 { x: 1, y: 0 };
 pub const W: Vector2 = Vector2 { x: -1, y: 0 };
 pub const S: Vector2 = Vector2 { x: 0, y: -1 };
 
+pub type Vector2i32 = Vector2<i32>;
+pub type Vector2i64 = Vector2<i64>;
+
+/// A point or offset in the plane. The coordinates are `isize` unless picked otherwise.
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Vector2 {
-    pub x: isize,
-    pub y: isize,
+pub struct Vector2<T = isize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Vector2 {
+impl<T: Coordinate> Vector2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vector2 { x, y }
+    }
 
-    pub const fn new_usize(x: usize, y: usize) -> Self {
-        Vector2 { x: x as isize, y: y as isize }
+    /// Panics if a coordinate does not fit in `T`.
+    pub fn new_usize(x: usize, y: usize) -> Self {
+        Vector2 {
+            x: T::from_usize(x),
+            y: T::from_usize(y),
+        }
     }
 
-    pub fn adjacent_points(&self) -> [Vector2; 4] {
-        [N, E, W, S].map(|d| *self + d)
+    /// The same vector with coordinates of another width, `None` if one does not fit.
+    pub fn try_cast<U: Coordinate + TryFrom<T>>(self) -> Option<Vector2<U>> {
+        Some(Vector2 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
     }
 
-    pub fn column(&self) -> isize {
+    pub fn adjacent_points(&self) -> [Vector2<T>; 4] {
+        let (zero, one) = (T::default(), T::from_usize(1));
+        [(zero, one), (one, zero), (-one, zero), (zero, -one)]
+            .map(|(x, y)| *self + Vector2 { x, y })
+    }
+
+    pub fn column(&self) -> T {
         self.x
     }
 
     pub fn column_index(&self) -> usize {
-        self.x as usize
+        self.x.to_usize()
     }
 
-    pub fn row(&self) -> isize {
+    pub fn row(&self) -> T {
         self.y
     }
 
     pub fn row_index(&self) -> usize {
-        self.y as usize
+        self.y.to_usize()
     }
 
+    /// Panics if the distance does not fit in a `usize`.
     pub fn manhattan_distance(&self) -> usize {
-        self.x.unsigned_abs() + self.y.unsigned_abs()
+        distance([self.x.abs_usize(), self.y.abs_usize()])
+    }
+
+    /// Panics if the distance does not fit in a `usize`.
+    pub fn distance_to(&self, other: Vector2<T>) -> usize {
+        distance([
+            self.x.abs_diff_usize(other.x),
+            self.y.abs_diff_usize(other.y),
+        ])
+    }
+}
+
+/// Which way `y` grows. Puzzles that draw a map count rows down from the top, like
+/// `Grid`, others put north at larger `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YAxis {
+    Up,
+    Down,
+}
+
+/// A compass direction, in clockwise order from north. Up on a map is north.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// The four directions along the axes, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// Turns clockwise by `eighths` of a full turn, counter-clockwise if negative.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    /// One step in this direction, with `y` growing the way `axis` says.
+    pub fn unit<T: Coordinate>(self, axis: YAxis) -> Vector2<T> {
+        let (zero, one) = (T::default(), T::from_usize(1));
+        let (x, north) = match self {
+            Direction::N => (zero, one),
+            Direction::NE => (one, one),
+            Direction::E => (one, zero),
+            Direction::SE => (one, -one),
+            Direction::S => (zero, -one),
+            Direction::SW => (-one, -one),
+            Direction::W => (-one, zero),
+            Direction::NW => (-one, one),
+        };
+        match axis {
+            YAxis::Up => Vector2 { x, y: north },
+            YAxis::Down => Vector2 { x, y: -north },
+        }
     }
+}
 
-    pub fn distance_to(&self, other: Vector2) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+/// `U`/`D`/`L`/`R`, the arrows `^`/`v`/`<`/`>` and `N`/`E`/`S`/`W`.
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Direction, String> {
+        match c {
+            'U' | '^' | 'N' => Ok(Direction::N),
+            'R' | '>' | 'E' => Ok(Direction::E),
+            'D' | 'v' | 'S' => Ok(Direction::S),
+            'L' | '<' | 'W' => Ok(Direction::W),
+            _ => Err(format!("{:?} is not a direction", c)),
+        }
     }
 }
 
-impl TryFrom<(usize, usize)> for Vector2 {
+/// A single character direction as for `TryFrom<char>`, or a diagonal like `NE`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Direction, String> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, _) => Direction::try_from(c),
+            (Some('N'), Some('E'), None) => Ok(Direction::NE),
+            (Some('S'), Some('E'), None) => Ok(Direction::SE),
+            (Some('S'), Some('W'), None) => Ok(Direction::SW),
+            (Some('N'), Some('W'), None) => Ok(Direction::NW),
+            _ => Err(format!("{:?} is not a direction", text)),
+        }
+    }
+}
+
+impl<T: Coordinate> TryFrom<(usize, usize)> for Vector2<T> {
     type Error = &'static str;
 
     fn try_from(value: (usize, usize)) -> Result<Self, Self::Error> {
-        let x = isize::try_from(value.0).map_err(|_| "x value is too large for isize")?;
-        let y = isize::try_from(value.1).map_err(|_| "y value is too large for isize")?;
+        let x = T::try_from(value.0).map_err(|_| "x value is too large for the coordinate type")?;
+        let y = T::try_from(value.1).map_err(|_| "y value is too large for the coordinate type")?;
         Ok(Vector2 { x, y })
     }
 }
 
+impl<T: Coordinate> Add<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-impl Add<Vector2> for Vector2 {
-    type Output = Vector2;
-
-    fn add(self, rhs: Vector2) -> Self::Output {
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -70,10 +219,10 @@ impl Add<Vector2> for Vector2 {
     }
 }
 
-impl Add<&Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Add<&Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: &Vector2) -> Self::Output {
+    fn add(self, rhs: &Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -81,10 +230,10 @@ impl Add<&Vector2> for Vector2 {
     }
 }
 
-impl Add<&(isize, isize)> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Add<&(T, T)> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: &(isize, isize)) -> Self::Output {
+    fn add(self, rhs: &(T, T)) -> Self::Output {
         Vector2 {
             x: self.x + rhs.0,
             y: self.y + rhs.1,
@@ -92,10 +241,10 @@ impl Add<&(isize, isize)> for Vector2 {
     }
 }
 
-impl Add<(isize, isize)> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Add<(T, T)> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: (isize, isize)) -> Self::Output {
+    fn add(self, rhs: (T, T)) -> Self::Output {
         Vector2 {
             x: self.x + rhs.0,
             y: self.y + rhs.1,
@@ -103,35 +252,35 @@ impl Add<(isize, isize)> for Vector2 {
     }
 }
 
-impl Add<(usize, usize)> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Add<(usize, usize)> for Vector2<T> {
+    type Output = Vector2<T>;
 
     fn add(self, rhs: (usize, usize)) -> Self::Output {
         Vector2 {
-            x: self.x + rhs.0 as isize,
-            y: self.y + rhs.1 as isize,
+            x: self.x + T::from_usize(rhs.0),
+            y: self.y + T::from_usize(rhs.1),
         }
     }
 }
 
-impl AddAssign<Vector2> for Vector2 {
-    fn add_assign(&mut self, rhs: Vector2) {
+impl<T: Coordinate> AddAssign<Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, rhs: Vector2<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
     }
 }
 
-impl AddAssign<&Vector2> for Vector2 {
-    fn add_assign(&mut self, rhs: &Vector2) {
+impl<T: Coordinate> AddAssign<&Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, rhs: &Vector2<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
     }
 }
 
-impl Sub<Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Sub<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, rhs: Vector2) -> Self::Output {
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -139,10 +288,10 @@ impl Sub<Vector2> for Vector2 {
     }
 }
 
-impl Sub<&Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Coordinate> Sub<&Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, rhs: &Vector2) -> Self::Output {
+    fn sub(self, rhs: &Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -150,25 +299,25 @@ impl Sub<&Vector2> for Vector2 {
     }
 }
 
-impl SubAssign<Vector2> for Vector2 {
-    fn sub_assign(&mut self, rhs: Vector2) {
+impl<T: Coordinate> SubAssign<Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: Vector2<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
     }
 }
 
-impl SubAssign<&Vector2> for Vector2 {
-    fn sub_assign(&mut self, rhs: &Vector2) {
+impl<T: Coordinate> SubAssign<&Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: &Vector2<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
     }
 }
 
-impl std::ops::Mul<usize> for Vector2 {
+impl<T: Coordinate> std::ops::Mul<usize> for Vector2<T> {
     type Output = Self;
 
     fn mul(self, rhs: usize) -> Self::Output {
-        let rhs = rhs as isize;
+        let rhs = T::from_usize(rhs);
 
         Vector2 {
             x: self.x * rhs,
@@ -177,3 +326,95 @@ impl std::ops::Mul<usize> for Vector2 {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works_for_every_width() {
+        let a = Vector2::<i64>::new(3_000_000_000, -4);
+        let b = Vector2::new(-1, 2);
+
+        assert_eq!(a + b, Vector2::new(2_999_999_999, -2));
+        assert_eq!(a.distance_to(b), 3_000_000_007);
+        assert_eq!(Vector2::<i8>::new(-128, 5).manhattan_distance(), 133);
+        assert_eq!(
+            Vector2::<i32>::new(1, 1) * 3 - Vector2::new_usize(1, 0),
+            Vector2::new(2, 3)
+        );
+        assert_eq!(N.adjacent_points()[0], Vector2 { x: 0, y: 2 });
+    }
+
+    #[test]
+    fn conversions_are_checked() {
+        assert_eq!(
+            Vector2::<i64>::new(7, -7).try_cast::<i8>(),
+            Some(Vector2::new(7i8, -7))
+        );
+        assert_eq!(Vector2::<i64>::new(300, 0).try_cast::<i8>(), None);
+        assert!(Vector2::<i8>::try_from((200, 0)).is_err());
+        assert_eq!(Vector2::<i64>::new(-1, 0).column_index(), usize::MAX);
+        assert_eq!(Vector2::<i128>::new(0, i128::MAX).row_index(), usize::MAX);
+        assert_eq!(
+            Vector2::<i128>::new(1 << 40, -(1 << 40)).manhattan_distance(),
+            1 << 41
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a usize")]
+    fn distances_that_do_not_fit_panic() {
+        Vector2::<i128>::new(i128::MIN, 0).distance_to(Vector2::new(i128::MAX, 0));
+    }
+
+    #[test]
+    fn turns_are_exact() {
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::SW.turn_left(), Direction::SE);
+        assert_eq!(Direction::E.reverse(), Direction::W);
+        assert_eq!(Direction::NW.rotate(1), Direction::N);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(
+                d.reverse().unit::<i32>(YAxis::Up),
+                Vector2::default() - d.unit(YAxis::Up)
+            );
+            assert_eq!(d.is_diagonal(), !Direction::CARDINAL.contains(&d));
+        }
+    }
+
+    #[test]
+    fn units_follow_the_axis() {
+        assert_eq!(Direction::N.unit(YAxis::Up), N);
+        assert_eq!(Direction::S.unit(YAxis::Up), S);
+        assert_eq!(Direction::N.unit::<isize>(YAxis::Down), S);
+        assert_eq!(Direction::SE.unit::<i64>(YAxis::Down), Vector2::new(1, 1));
+    }
+
+    #[test]
+    fn directions_parse() {
+        for (text, direction) in [
+            ("U", Direction::N),
+            ("v", Direction::S),
+            ("<", Direction::W),
+            ("E", Direction::E),
+            ("NE", Direction::NE),
+        ] {
+            assert_eq!(text.parse(), Ok(direction));
+        }
+        for text in ["", "X", "up", "NN", "NEE"] {
+            assert!(
+                text.parse::<Direction>().is_err(),
+                "{:?} should be rejected",
+                text
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn oversized_usize_panics() {
+        Vector2::<i8>::new_usize(128, 0);
+    }
+}
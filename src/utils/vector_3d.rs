@@ -1,45 +1,150 @@
+//! Points and offsets in space, with their 6 face neighbours, all 26 surrounding points
+//! and the 24 orientations of a cube in `Rotation::ALL`.
+
 #![allow(dead_code)]
 
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
+use super::coordinate::{distance, Coordinate};
+
+pub type Vector3D = Vector3<isize>;
+pub type Vector3i32 = Vector3<i32>;
+
+/// A point or offset in space. The coordinates are `isize` unless picked otherwise.
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Vector3D {
-    pub x: isize,
-    pub y: isize,
-    pub z: isize,
+pub struct Vector3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Vector3D {
-    pub const fn new(x: isize, y: isize, z: isize) -> Self {
-        Vector3D { x, y, z }
+impl<T: Coordinate> Vector3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vector3 { x, y, z }
+    }
+
+    /// The same vector with coordinates of another width, `None` if one does not fit.
+    pub fn try_cast<U: Coordinate + TryFrom<T>>(self) -> Option<Vector3<U>> {
+        Some(Vector3 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+            z: U::try_from(self.z).ok()?,
+        })
     }
 
-    pub fn adjacent_points(&self) -> [Vector3D; 6] {
+    pub fn adjacent_points(&self) -> [Vector3<T>; 6] {
+        let (zero, one) = (T::default(), T::from_usize(1));
         [
-            (-1, 0, 0),
-            (1, 0, 0),
-            (0, -1, 0),
-            (0, 1, 0),
-            (0, 0, -1),
-            (0, 0, 1),
+            (-one, zero, zero),
+            (one, zero, zero),
+            (zero, -one, zero),
+            (zero, one, zero),
+            (zero, zero, -one),
+            (zero, zero, one),
         ]
-        .map(|(x_d, y_d, z_d)| Vector3D::new(self.x + x_d, self.y + y_d, self.z + z_d))
+        .map(|(x_d, y_d, z_d)| Vector3::new(self.x + x_d, self.y + y_d, self.z + z_d))
     }
 
+    /// Every point that shares a face, edge or corner with this one, in `z`, `y`, `x` order.
+    pub fn surrounding_points(&self) -> [Vector3<T>; 26] {
+        let steps = [-T::from_usize(1), T::default(), T::from_usize(1)];
+        let mut points = [*self; 26];
+        let mut i = 0;
+        for dz in steps {
+            for dy in steps {
+                for dx in steps {
+                    if (dx, dy, dz) != (T::default(), T::default(), T::default()) {
+                        points[i] = Vector3::new(self.x + dx, self.y + dy, self.z + dz);
+                        i += 1;
+                    }
+                }
+            }
+        }
+        points
+    }
+
+    pub fn rotate(self, rotation: &Rotation) -> Vector3<T> {
+        let coordinates = [self.x, self.y, self.z];
+        let axis = |i: usize| match rotation.negate[i] {
+            true => -coordinates[rotation.axes[i]],
+            false => coordinates[rotation.axes[i]],
+        };
+        Vector3::new(axis(0), axis(1), axis(2))
+    }
+
+    /// This vector in each of the 24 orientations of `Rotation::ALL`.
+    pub fn rotations(self) -> [Vector3<T>; 24] {
+        Rotation::ALL.map(|rotation| self.rotate(&rotation))
+    }
+
+    /// Panics if the distance does not fit in a `usize`.
     pub fn manhattan_distance(&self) -> usize {
-        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+        distance([self.x.abs_usize(), self.y.abs_usize(), self.z.abs_usize()])
     }
 
-    pub fn distance_to(&self, other: Vector3D) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    /// Panics if the distance does not fit in a `usize`.
+    pub fn distance_to(&self, other: Vector3<T>) -> usize {
+        distance([
+            self.x.abs_diff_usize(other.x),
+            self.y.abs_diff_usize(other.y),
+            self.z.abs_diff_usize(other.z),
+        ])
     }
 }
 
-impl Add<Vector3D> for Vector3D {
-    type Output = Vector3D;
+/// One of the 24 ways to turn a cube onto its faces: coordinate `i` of a rotated vector is
+/// coordinate `axes[i]` of the original, negated if `negate[i]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    pub axes: [usize; 3],
+    pub negate: [bool; 3],
+}
+
+impl Rotation {
+    /// The identity first. Each permutation of the axes comes with the sign flips that keep
+    /// the coordinate system right-handed, the others would be mirror images.
+    pub const ALL: [Rotation; 24] = Rotation::all();
+
+    const fn all() -> [Rotation; 24] {
+        // Each permutation with whether it is even.
+        const PERMUTATIONS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], true),
+            ([1, 2, 0], true),
+            ([2, 0, 1], true),
+            ([0, 2, 1], false),
+            ([2, 1, 0], false),
+            ([1, 0, 2], false),
+        ];
 
-    fn add(self, rhs: Vector3D) -> Self::Output {
-        Vector3D {
+        let mut rotations = [Rotation {
+            axes: [0, 1, 2],
+            negate: [false; 3],
+        }; 24];
+        let mut n = 0;
+        let mut p = 0;
+        while p < PERMUTATIONS.len() {
+            let (axes, even) = PERMUTATIONS[p];
+            let mut signs = 0;
+            while signs < 8 {
+                let odd_flips = (signs as u32).count_ones() % 2 == 1;
+                if even != odd_flips {
+                    let negate = [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0];
+                    rotations[n] = Rotation { axes, negate };
+                    n += 1;
+                }
+                signs += 1;
+            }
+            p += 1;
+        }
+        rotations
+    }
+}
+
+impl<T: Coordinate> Add<Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Vector3 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
             z: self.z + rhs.z,
@@ -47,11 +152,11 @@ impl Add<Vector3D> for Vector3D {
     }
 }
 
-impl Add<&Vector3D> for Vector3D {
-    type Output = Vector3D;
+impl<T: Coordinate> Add<&Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
 
-    fn add(self, rhs: &Vector3D) -> Self::Output {
-        Vector3D {
+    fn add(self, rhs: &Vector3<T>) -> Self::Output {
+        Vector3 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
             z: self.z + rhs.z,
@@ -59,27 +164,27 @@ impl Add<&Vector3D> for Vector3D {
     }
 }
 
-impl AddAssign<Vector3D> for Vector3D {
-    fn add_assign(&mut self, rhs: Vector3D) {
+impl<T: Coordinate> AddAssign<Vector3<T>> for Vector3<T> {
+    fn add_assign(&mut self, rhs: Vector3<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
         self.z += rhs.z;
     }
 }
 
-impl AddAssign<&Vector3D> for Vector3D {
-    fn add_assign(&mut self, rhs: &Vector3D) {
+impl<T: Coordinate> AddAssign<&Vector3<T>> for Vector3<T> {
+    fn add_assign(&mut self, rhs: &Vector3<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
         self.z += rhs.z;
     }
 }
 
-impl Sub<Vector3D> for Vector3D {
-    type Output = Vector3D;
+impl<T: Coordinate> Sub<Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
 
-    fn sub(self, rhs: Vector3D) -> Self::Output {
-        Vector3D {
+    fn sub(self, rhs: Vector3<T>) -> Self::Output {
+        Vector3 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
             z: self.z - rhs.z,
@@ -87,11 +192,11 @@ impl Sub<Vector3D> for Vector3D {
     }
 }
 
-impl Sub<&Vector3D> for Vector3D {
-    type Output = Vector3D;
+impl<T: Coordinate> Sub<&Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
 
-    fn sub(self, rhs: &Vector3D) -> Self::Output {
-        Vector3D {
+    fn sub(self, rhs: &Vector3<T>) -> Self::Output {
+        Vector3 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
             z: self.z - rhs.z,
@@ -99,32 +204,93 @@ impl Sub<&Vector3D> for Vector3D {
     }
 }
 
-impl SubAssign<Vector3D> for Vector3D {
-    fn sub_assign(&mut self, rhs: Vector3D) {
+impl<T: Coordinate> SubAssign<Vector3<T>> for Vector3<T> {
+    fn sub_assign(&mut self, rhs: Vector3<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
         self.z -= rhs.z;
     }
 }
 
-impl SubAssign<&Vector3D> for Vector3D {
-    fn sub_assign(&mut self, rhs: &Vector3D) {
+impl<T: Coordinate> SubAssign<&Vector3<T>> for Vector3<T> {
+    fn sub_assign(&mut self, rhs: &Vector3<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
         self.z -= rhs.z;
     }
 }
 
-impl std::ops::Mul<usize> for Vector3D {
+impl<T: Coordinate> std::ops::Mul<usize> for Vector3<T> {
     type Output = Self;
 
     fn mul(self, rhs: usize) -> Self::Output {
-        let rhs = rhs as isize;
+        let rhs = T::from_usize(rhs);
 
-        Vector3D {
+        Vector3 {
             x: self.x * rhs,
             y: self.y * rhs,
             z: self.z * rhs,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works_for_every_width() {
+        let a = Vector3i32::new(1, -2, 3);
+
+        assert_eq!(a + Vector3::new(1, 1, 1), Vector3::new(2, -1, 4));
+        assert_eq!(a.manhattan_distance(), 6);
+        assert_eq!(a.distance_to(Vector3::default()), 6);
+        assert_eq!(a * 2, Vector3::new(2, -4, 6));
+        assert_eq!(
+            a.adjacent_points()
+                .iter()
+                .filter(|p| p.distance_to(a) == 1)
+                .count(),
+            6
+        );
+        assert_eq!(a.try_cast::<i8>(), Some(Vector3::new(1i8, -2, 3)));
+        assert_eq!(Vector3D::new(0, 0, 1 << 40).try_cast::<i32>(), None);
+    }
+
+    #[test]
+    fn surrounding_points_are_distinct() {
+        let points = Vector3i32::new(5, 5, 5).surrounding_points();
+        let distinct = points.iter().collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(distinct.len(), 26);
+        assert!(points
+            .iter()
+            .all(|p| (p.x - 5).abs() <= 1 && (p.y - 5).abs() <= 1 && (p.z - 5).abs() <= 1));
+        assert!(!distinct.contains(&Vector3::new(5, 5, 5)));
+    }
+
+    #[test]
+    fn rotations_are_the_24_proper_ones() {
+        let v = Vector3i32::new(1, 2, 3);
+        let rotations = v.rotations();
+        let distinct = rotations.iter().collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(rotations[0], v);
+        assert_eq!(distinct.len(), 24);
+        assert!(rotations.iter().all(|r| r.manhattan_distance() == 6));
+
+        // A proper rotation keeps the cross product: rotating x and y gives the rotated z.
+        for rotation in &Rotation::ALL {
+            let (x, y) = (
+                Vector3i32::new(1, 0, 0).rotate(rotation),
+                Vector3i32::new(0, 1, 0).rotate(rotation),
+            );
+            let cross = Vector3::new(
+                x.y * y.z - x.z * y.y,
+                x.z * y.x - x.x * y.z,
+                x.x * y.y - x.y * y.x,
+            );
+            assert_eq!(cross, Vector3::new(0, 0, 1).rotate(rotation));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Sets of points inside a `Cuboid`, for days that flood or fill a small 3D space, like
+//! the air around day 18's droplet.
+
+use super::{coordinate::Coordinate, cuboid::Cuboid, vector_3d::Vector3};
+
+/// A set of points inside fixed bounds, one flag per point of the bounds. Membership is
+/// an index into a `Vec`, much faster than hashing for the small dense shapes of 3D days.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Voxels<T = isize> {
+    bounds: Cuboid<T>,
+    cells: Vec<bool>,
+    len: usize,
+}
+
+impl<T: Coordinate> Voxels<T> {
+    /// The most points the bounds of a set may hold, a 256 MiB `Vec`. Sparse points far
+    /// apart belong in a `HashSet`.
+    pub const MAX_VOLUME: usize = 1 << 28;
+
+    /// An empty set that can hold the points of `bounds`. Panics if they hold more than
+    /// `MAX_VOLUME` points.
+    pub fn new(bounds: Cuboid<T>) -> Voxels<T> {
+        match Voxels::try_new(bounds) {
+            Some(voxels) => voxels,
+            None => panic!("{:?} holds more than {} points", bounds, Self::MAX_VOLUME),
+        }
+    }
+
+    /// An empty set that can hold the points of `bounds`, `None` if they hold more than
+    /// `MAX_VOLUME` points.
+    pub fn try_new(bounds: Cuboid<T>) -> Option<Voxels<T>> {
+        let volume = bounds.checked_volume().filter(|&v| v <= Self::MAX_VOLUME)?;
+        Some(Voxels {
+            bounds,
+            cells: vec![false; volume],
+            len: 0,
+        })
+    }
+
+    pub fn bounds(&self) -> &Cuboid<T> {
+        &self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether `point` is in the set, `false` outside the bounds.
+    pub fn contains(&self, point: Vector3<T>) -> bool {
+        self.bounds.index_of(point).is_some_and(|i| self.cells[i])
+    }
+
+    /// Adds `point`, returning whether it was new. Panics if it is outside the bounds.
+    pub fn insert(&mut self, point: Vector3<T>) -> bool {
+        let index = match self.bounds.index_of(point) {
+            Some(index) => index,
+            None => panic!("{:?} is outside the bounds {:?}", point, self.bounds),
+        };
+        let new = !self.cells[index];
+        self.cells[index] = true;
+        self.len += usize::from(new);
+        new
+    }
+
+    /// Removes `point`, returning whether it was there.
+    pub fn remove(&mut self, point: Vector3<T>) -> bool {
+        match self.bounds.index_of(point) {
+            Some(index) if self.cells[index] => {
+                self.cells[index] = false;
+                self.len -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The points in the set, in the order of `Cuboid::points`.
+    pub fn iter(&self) -> impl Iterator<Item = Vector3<T>> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &set)| set)
+            .map(|(i, _)| self.bounds.point_at(i))
+    }
+}
+
+/// A set exactly as large as the points need, empty bounds at the origin if there are none.
+/// Panics like `new` if the points are too far apart.
+impl<T: Coordinate> FromIterator<Vector3<T>> for Voxels<T> {
+    fn from_iter<I: IntoIterator<Item = Vector3<T>>>(iter: I) -> Self {
+        let points = iter.into_iter().collect::<Vec<_>>();
+        let bounds = Cuboid::from_points(points.iter().copied());
+        let mut voxels =
+            Voxels::new(bounds.unwrap_or(Cuboid::new(Vector3::default(), Vector3::default())));
+        for point in points {
+            voxels.insert(point);
+        }
+        voxels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut voxels = Voxels::<i32>::new(Cuboid::new(Vector3::default(), Vector3::new(2, 2, 2)));
+
+        assert!(voxels.insert(Vector3::new(1, 2, 0)));
+        assert!(!voxels.insert(Vector3::new(1, 2, 0)));
+        assert!(voxels.contains(Vector3::new(1, 2, 0)));
+        assert!(!voxels.contains(Vector3::new(1, 3, 0)));
+        assert_eq!(voxels.len(), 1);
+        assert!(voxels.remove(Vector3::new(1, 2, 0)));
+        assert!(!voxels.remove(Vector3::new(1, 2, 0)));
+        assert!(voxels.is_empty());
+    }
+
+    #[test]
+    fn collects_into_tight_bounds() {
+        let points = [
+            Vector3::new(3, -1, 2),
+            Vector3::new(1, 1, 1),
+            Vector3::new(3, -1, 2),
+        ];
+        let voxels = points.into_iter().collect::<Voxels<i64>>();
+
+        assert_eq!(voxels.len(), 2);
+        assert_eq!(voxels.bounds().size(), [3, 3, 2]);
+        assert_eq!(
+            voxels.iter().collect::<Vec<_>>(),
+            [Vector3::new(1, 1, 1), Vector3::new(3, -1, 2)]
+        );
+    }
+
+    #[test]
+    fn bounds_too_large_are_refused() {
+        let far = Cuboid::<i32>::new(
+            Vector3::new(1, 1, 1),
+            Vector3::new(100_000, 100_000, 100_000),
+        );
+        assert_eq!(Voxels::try_new(far), None);
+        let widest = Cuboid::<i64>::new(Vector3::new(i64::MIN, 0, 0), Vector3::new(i64::MAX, 0, 0));
+        assert_eq!(Voxels::try_new(widest), None);
+        assert!(Voxels::try_new(Cuboid::<i32>::new(
+            Vector3::default(),
+            Vector3::new(9, 9, 9)
+        ))
+        .is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the bounds")]
+    fn points_outside_are_rejected() {
+        Voxels::<i32>::new(Cuboid::new(Vector3::default(), Vector3::default()))
+            .insert(Vector3::new(1, 0, 0));
+    }
+}
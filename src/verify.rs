@@ -0,0 +1,124 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    etc::{
+        answers::{Answers, Verdict},
+        input::{self, REAL_INPUT},
+        solver::Solver,
+    },
+    registry::Event,
+    Solution,
+};
+
+/// What `verify` found for one day.
+#[derive(Debug)]
+pub struct DayCheck {
+    pub day: u8,
+    pub title: &'static str,
+    pub inputs: Vec<InputCheck>,
+}
+
+/// The checked parts of one input, or why it could not be solved.
+#[derive(Debug)]
+pub struct InputCheck {
+    pub name: String,
+    pub parts: Result<Vec<PartCheck>, String>,
+}
+
+#[derive(Debug)]
+pub struct PartCheck {
+    pub part: u8,
+    pub actual: Solution,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+    pub unsolved: usize,
+}
+
+impl Summary {
+    /// The verdicts of `days` counted up. An input that could not be solved counts as
+    /// one failure.
+    pub fn of(days: &[DayCheck]) -> Summary {
+        let mut summary = Summary::default();
+        for input in days.iter().flat_map(|d| &d.inputs) {
+            match &input.parts {
+                Ok(parts) => parts.iter().for_each(|p| summary.record(&p.verdict)),
+                Err(_) => summary.failed += 1,
+            }
+        }
+        summary
+    }
+
+    fn record(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail(_) => self.failed += 1,
+            Verdict::Missing => self.missing += 1,
+            Verdict::Unsolved => self.unsolved += 1,
+        }
+    }
+}
+
+/// Runs each day against its real input, its example input and every other
+/// input with recorded answers, and compares `parts` to `answers`. Each input is solved
+/// with the parameters recorded for it. Days without a solver are left out.
+pub fn verify(event: &Event, answers: &Answers, days: &[u8], parts: &[u8]) -> Vec<DayCheck> {
+    days.iter()
+        .filter_map(|&day| event.solver(day))
+        .map(|solver| {
+            let listed = answers.inputs(solver.day());
+            let mut names = BTreeSet::from([REAL_INPUT, solver.test_input()]);
+            names.extend(&listed);
+
+            DayCheck {
+                day: solver.day(),
+                title: solver.title(),
+                inputs: names
+                    .into_iter()
+                    .filter_map(|name| {
+                        check_input(event, answers, solver, name, listed.contains(&name), parts)
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Solves the input `name` and checks `parts` of it. A missing file is only an error if
+/// the input is `listed` in `answers`.
+fn check_input(
+    event: &Event,
+    answers: &Answers,
+    solver: &dyn Solver,
+    name: &str,
+    listed: bool,
+    parts: &[u8],
+) -> Option<InputCheck> {
+    let day = solver.day();
+    let checked = match input::read_input(&input::input_path(event.input_dir, day, name)) {
+        Err(_) if !listed => return None,
+        Err(e) => Err(e.to_string()),
+        Ok(text) => match solver.solve_with(&text, &answers.params(day, name), false) {
+            Err(e) => Err(e.to_string()),
+            Ok(((p1, p2), _, _)) => Ok([(1, p1), (2, p2)]
+                .into_iter()
+                .filter(|(part, _)| parts.contains(part))
+                .map(|(part, actual)| PartCheck {
+                    part,
+                    verdict: answers.check(day, usize::from(part), name, &actual),
+                    actual,
+                })
+                .collect()),
+        },
+    };
+
+    Some(InputCheck {
+        name: name.to_owned(),
+        parts: checked,
+    })
+}
@@ -0,0 +1,72 @@
+use std::{
+    panic,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// Runs `work` on a worker thread and waits at most `budget` for its result, `None` if it
+/// ran out of time. Threads cannot be stopped from outside, so a worker that times out is
+/// left to finish in the background while the caller moves on. Without a budget `work`
+/// simply runs on the calling thread. A panic in `work` is passed on to the caller.
+pub fn run_with_budget<T, F>(budget: Option<Duration>, work: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let budget = match budget {
+        Some(budget) => budget,
+        None => return Some(work()),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        // The receiver is gone if the budget ran out, nobody wants the result then.
+        let _ = sender.send(work());
+    });
+
+    match receiver.recv_timeout(budget) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the worker exited without sending a result"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn finishes_within_budget() {
+        assert_eq!(
+            run_with_budget(Some(Duration::from_secs(10)), || 7),
+            Some(7)
+        );
+        assert_eq!(run_with_budget(None, || 7), Some(7));
+    }
+
+    #[test]
+    fn gives_up_when_the_budget_runs_out() {
+        let start = Instant::now();
+        let result = run_with_budget(Some(Duration::from_millis(20)), || {
+            thread::sleep(Duration::from_secs(2));
+            7
+        });
+
+        assert_eq!(result, None);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "solver bug")]
+    fn passes_panics_on() {
+        run_with_budget(Some(Duration::from_secs(10)), || -> u8 {
+            panic!("solver bug")
+        });
+    }
+}
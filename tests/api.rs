@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+use advent_of_code_template::{
+    days::day13::compare,
+    etc::{
+        answers::{Answers, Verdict},
+        stats::{Stat, Stats},
+    },
+    registry,
+    utils::vector_2d::{Vector2, E, N},
+    verify::{self, Summary},
+    Solution,
+};
+use serde_json::json;
+
+#[test]
+fn run_solves_a_day() {
+    let input = include_str!("../input/day01/test.txt");
+    let (p1, p2) = advent_of_code_template::run(1, input).unwrap();
+
+    assert_eq!(p1.to_string(), "24000");
+    assert_eq!(p2.to_string(), "45000");
+}
+
+#[test]
+fn run_reports_where_input_is_wrong() {
+    let error = advent_of_code_template::run(4, "2-4,6-8\n2-x,4-5\n").unwrap_err();
+    let location = error.location.unwrap();
+
+    assert_eq!(error.day, 4);
+    assert_eq!((location.line, location.column), (2, 3));
+}
+
+#[test]
+fn run_rejects_unregistered_days() {
+    let error = advent_of_code_template::run(26, "").unwrap_err();
+
+    assert_eq!(error.day, 26);
+    assert!(advent_of_code_template::run_year(1999, 1, "").is_err());
+}
+
+#[test]
+fn solvers_record_stats_on_request() {
+    let solver = registry::latest().solver(24).unwrap();
+    let input = include_str!("../input/day24/test.txt");
+    let (solutions, _, [one, two]) = solver.solve_with_stats(input).unwrap();
+
+    assert_eq!(solutions, solver.solve(input).unwrap());
+    assert!(matches!(one.stats(), [("heap pops", Stat::Count(n))] if *n > 0));
+    assert!(two.counter("heap pops") > one.counter("heap pops"));
+    assert!(registry::latest()
+        .solver(1)
+        .unwrap()
+        .solve_with_stats("1\n\n2\n\n3")
+        .unwrap()
+        .2[0]
+        .is_empty());
+}
+
+#[test]
+fn registry_lists_every_day() {
+    let event = registry::latest();
+    let days = event.select(&registry::Days::All).unwrap();
+
+    assert_eq!(days, (1..=25).collect::<Vec<_>>());
+    assert_eq!(event.solver(13).unwrap().title(), "Distress Signal");
+}
+
+#[test]
+fn verify_returns_verdicts() {
+    let event = registry::latest();
+    let answers = Answers::load(event.input_dir).unwrap();
+    let checked = verify::verify(event, &answers, &[1, 25], &[2]);
+
+    let test = checked[0].inputs.iter().find(|i| i.name == "test").unwrap();
+    let parts = test.parts.as_ref().unwrap();
+    assert_eq!(
+        (parts.len(), parts[0].part, &parts[0].verdict),
+        (1, 2, &Verdict::Pass)
+    );
+    assert_eq!(parts[0].actual.to_string(), "45000");
+    assert_eq!(checked[1].title, "Full of Hot Air");
+    assert!(Summary::of(&checked).unsolved > 0);
+}
+
+#[test]
+fn packets_compare() {
+    assert_eq!(
+        compare(&json!([1, 1, 3]), &json!([1, 1, 5])),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare(&json!([[4, 4], 4]), &json!([[4, 4], 4, 4])),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare(&json!([7, 7, 7]), &json!([7, 7])),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn vectors_add() {
+    assert_eq!(Vector2::new_usize(1, 1) + N + E, Vector2::new_usize(2, 2));
+}
+
+#[test]
+fn solutions_display() {
+    assert_eq!(Solution::Str("CMZ".to_owned()).to_string(), "CMZ");
+    assert_eq!(Solution::I64(-3).to_string(), "-3");
+}
@@ -0,0 +1,170 @@
+//! Runs every `input/dayNN/test*.txt` through its solver and checks both parts against
+//! the answers `input/answers.json` records for it, and their `Solution` variants, or the
+//! error a solver rejects the example with, against the table below. Each example is solved
+//! with the parameters `input/answers.json` records for it, once with LF and once with CRLF
+//! line endings.
+
+use std::{fs, path::Path};
+
+use advent_of_code_template::{
+    etc::answers::{Answers, Verdict},
+    registry, Solution,
+};
+
+use Expected::*;
+
+enum Expected {
+    /// The `Solution::kind` of each part, whose values are in `answers.json`.
+    Kinds(&'static str, &'static str),
+    /// The solver rejects the input with this message.
+    Error(&'static str),
+}
+
+fn table() -> Vec<(u8, &'static str, Expected)> {
+    vec![
+        (1, "test", Kinds("USize", "USize")),
+        (2, "test", Kinds("USize", "USize")),
+        (3, "test", Kinds("USize", "USize")),
+        (4, "test", Kinds("USize", "USize")),
+        (5, "test", Kinds("Str", "Str")),
+        (6, "test", Kinds("USize", "USize")),
+        (7, "test", Kinds("I32", "I32")),
+        (8, "test", Kinds("I32", "U32")),
+        (9, "test", Kinds("USize", "USize")),
+        (10, "test", Error("program runs for fewer than 240 cycles")),
+        (10, "test2", Kinds("I32", "Str")),
+        (11, "test", Kinds("I64", "I64")),
+        (12, "test", Kinds("U32", "U32")),
+        (13, "test", Kinds("USize", "USize")),
+        (14, "test", Kinds("USize", "USize")),
+        (15, "test", Kinds("I64", "I64")),
+        (16, "test", Kinds("USize", "Unsolved")),
+        (17, "test", Kinds("USize", "USize")),
+        (18, "test", Kinds("USize", "USize")),
+        (19, "test", Kinds("Unsolved", "Unsolved")),
+        (20, "test", Kinds("ISize", "ISize")),
+        (21, "test", Kinds("I64", "I64")),
+        (22, "test", Kinds("USize", "Unsolved")),
+        (23, "test", Kinds("USize", "USize")),
+        (24, "test", Kinds("USize", "USize")),
+        (25, "test", Kinds("Str", "Unsolved")),
+    ]
+}
+
+/// Every `test*.txt` on disk that has been filled in, as `(day, name)`.
+fn example_inputs() -> Vec<(u8, String)> {
+    let mut inputs = Vec::new();
+    for day in 1..=25 {
+        let dir = Path::new("input").join(format!("day{:02}", day));
+        for entry in fs::read_dir(dir).into_iter().flatten() {
+            let entry = entry.unwrap();
+            if entry.metadata().unwrap().len() == 0 {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(name) = name
+                .strip_prefix("test")
+                .and_then(|n| n.strip_suffix(".txt"))
+            {
+                inputs.push((day, format!("test{}", name)));
+            }
+        }
+    }
+    inputs.sort();
+    inputs
+}
+
+#[test]
+fn every_example_has_an_entry() {
+    let examples = table();
+    for (day, name) in example_inputs() {
+        assert!(
+            examples.iter().any(|(d, n, _)| *d == day && *n == name),
+            "input/day{:02}/{}.txt has no entry in the examples table",
+            day,
+            name
+        );
+    }
+}
+
+/// What is wrong with `part` of the example `name`, if anything: its variant differs from
+/// `kind` or its value from the one in `answers.json`.
+fn check(
+    recorded: &Answers,
+    day: u8,
+    name: &str,
+    part: usize,
+    actual: &Solution,
+    kind: &str,
+) -> Option<String> {
+    if actual.kind() != kind {
+        return Some(format!(
+            "part {}: expected a {}, got {:?}",
+            part, kind, actual
+        ));
+    }
+    match recorded.check(day, part, name, actual) {
+        Verdict::Pass | Verdict::Unsolved => None,
+        Verdict::Fail(expected) => Some(format!(
+            "part {}: expected {:?}, got {:?}",
+            part,
+            expected,
+            actual.to_string()
+        )),
+        Verdict::Missing => Some(format!(
+            "part {}: got {:?}, which answers.json does not record",
+            part,
+            actual.to_string()
+        )),
+    }
+}
+
+/// `text` with LF and with CRLF line endings, whichever it was written with.
+fn line_endings(text: &str) -> [(&'static str, String); 2] {
+    let lf = text.replace("\r\n", "\n");
+    let crlf = lf.replace('\n', "\r\n");
+    [("LF", lf), ("CRLF", crlf)]
+}
+
+#[test]
+fn examples_match_table() {
+    let mut failures = Vec::new();
+    let recorded = Answers::load("input").unwrap();
+
+    for (day, name, expected) in table() {
+        let path = Path::new("input")
+            .join(format!("day{:02}", day))
+            .join(format!("{}.txt", name));
+        let input = fs::read_to_string(&path).unwrap();
+        let solver = registry::latest().solver(day).unwrap();
+
+        for (endings, input) in line_endings(&input) {
+            let actual = solver
+                .solve_with(&input, &recorded.params(day, name), false)
+                .map(|(solutions, _, _)| solutions);
+
+            let failure = match (&expected, actual) {
+                (Kinds(one, two), Ok((p1, p2))) => {
+                    let parts = [(1, p1, one), (2, p2, two)];
+                    let wrong = parts
+                        .iter()
+                        .filter_map(|(part, actual, kind)| {
+                            check(&recorded, day, name, *part, actual, kind)
+                        })
+                        .collect::<Vec<_>>();
+                    (!wrong.is_empty()).then(|| wrong.join(", "))
+                }
+                (Error(message), Err(e)) if e.message == *message => None,
+                (Kinds(..), Err(e)) => Some(format!("expected answers, got error {:?}", e.message)),
+                (Error(message), actual) => {
+                    Some(format!("expected error {:?}, got {:?}", message, actual))
+                }
+            };
+            if let Some(failure) = failure {
+                failures.push(format!("{} ({}): {}", path.display(), endings, failure));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}